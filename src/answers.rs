@@ -0,0 +1,83 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+/// Name of the file in the input directory listing the accepted answers
+pub const FILE_NAME: &str = "answers.txt";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Correct,
+    Wrong,
+    Unverified,
+    Failed,
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Status::Correct => write!(f, "correct"),
+            Status::Wrong => write!(f, "wrong"),
+            Status::Unverified => write!(f, "unverified"),
+            Status::Failed => write!(f, "failed"),
+        }
+    }
+}
+
+/// Accepted answers, one per line as `DAY PART ANSWER`. Blank lines and lines starting with `#`
+/// are ignored.
+#[derive(Debug, Default)]
+pub struct Answers(HashMap<(usize, usize), String>);
+
+impl Answers {
+    /// Load the answers from the input directory. A missing file just means nothing can be
+    /// verified.
+    pub fn load(input_dir: &Path) -> Answers {
+        let path = input_dir.join(FILE_NAME);
+        fs::read_to_string(&path)
+            .map(|text| Answers::parse(&text))
+            .unwrap_or_default()
+    }
+
+    fn parse(text: &str) -> Answers {
+        let answers = text
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .filter_map(|line| {
+                let mut fields = line.split_ascii_whitespace();
+                let day = fields.next()?.parse().ok()?;
+                let part = fields.next()?.parse().ok()?;
+                let answer = fields.next()?.to_string();
+                Some(((day, part), answer))
+            })
+            .collect();
+        Answers(answers)
+    }
+
+    /// Compare an answer against the accepted one, where `None` means the solution didn't produce
+    /// an answer at all
+    pub fn check(&self, day: usize, part: usize, answer: Option<&str>) -> Status {
+        match (answer, self.0.get(&(day, part))) {
+            (None, _) => Status::Failed,
+            (Some(_), None) => Status::Unverified,
+            (Some(answer), Some(expected)) if answer == expected => Status::Correct,
+            (Some(_), Some(_)) => Status::Wrong,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_answers() {
+        let answers = Answers::parse("# day part answer\n1 1 3\n1 2 6\n\n8 1 40\n");
+        assert_eq!(answers.check(1, 1, Some("3")), Status::Correct);
+        assert_eq!(answers.check(1, 2, Some("5")), Status::Wrong);
+        assert_eq!(answers.check(2, 1, Some("1227775554")), Status::Unverified);
+        assert_eq!(answers.check(8, 1, None), Status::Failed);
+    }
+}
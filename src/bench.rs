@@ -0,0 +1,116 @@
+use std::panic;
+use std::time::{Duration, Instant};
+
+use crate::allocations;
+
+#[derive(Debug, Clone)]
+pub struct Measurement {
+    /// The answer produced, or why there isn't one: the solution's error, or that it panicked
+    pub answer: Result<String, String>,
+    pub median: Duration,
+    pub min: Duration,
+    /// Allocations made by a single run
+    pub allocations: usize,
+    pub iterations: usize,
+}
+
+/// Run a solution repeatedly and collect its timings. Stops at the first error or panic.
+pub fn measure(
+    solution: fn(&str) -> Result<String, String>,
+    input: &str,
+    iterations: usize,
+) -> Measurement {
+    let mut answer = Err("no runs".to_string());
+    let mut allocations = 0;
+    let mut times = Vec::with_capacity(iterations);
+
+    for _ in 0..iterations {
+        let before = allocations::count();
+        let now = Instant::now();
+        let result =
+            panic::catch_unwind(|| solution(input)).unwrap_or_else(|_| Err("panicked".to_string()));
+        if let Err(err) = result {
+            return Measurement {
+                answer: Err(err),
+                median: Duration::ZERO,
+                min: Duration::ZERO,
+                allocations: 0,
+                iterations: 0,
+            };
+        }
+        times.push(now.elapsed());
+        // the answer itself is allocated by the solution, so it is counted before it is dropped
+        allocations = allocations::count() - before;
+        answer = result;
+    }
+    times.sort_unstable();
+
+    Measurement {
+        answer,
+        median: times[times.len() / 2],
+        min: times[0],
+        allocations,
+        iterations,
+    }
+}
+
+/// Run `f` `iterations` times, returning its last result and the median time it took
+pub fn time<T>(iterations: usize, mut f: impl FnMut() -> T) -> (T, Duration) {
+    let mut result = None;
+    let mut times = Vec::with_capacity(iterations);
+    for _ in 0..iterations.max(1) {
+        let now = Instant::now();
+        result = Some(f());
+        times.push(now.elapsed());
+    }
+    times.sort_unstable();
+    (result.unwrap(), times[times.len() / 2])
+}
+
+/// Print the median times of two implementations of the same thing, and how many times faster
+/// the first is
+pub fn print_comparison(first: (&str, Duration), second: (&str, Duration)) {
+    let width = first.0.len().max(second.0.len()) + 1;
+    for (name, median) in [first, second] {
+        let label = format!("{name}:");
+        println!("{label:<width$} median {}", format_duration(median));
+    }
+    println!(
+        "speedup {:.1}x",
+        second.1.as_secs_f64() / first.1.as_secs_f64()
+    );
+}
+
+/// Format a duration with a unit suited to its size, e.g. `85.3µs` or `1.25s`
+pub fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs_f64();
+    if secs < 1e-3 {
+        format!("{:.1}µs", secs * 1e6)
+    } else if secs < 1.0 {
+        format!("{:.2}ms", secs * 1e3)
+    } else {
+        format!("{secs:.2}s")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn timing() {
+        let mut calls = 0;
+        let (result, _) = time(5, || {
+            calls += 1;
+            calls
+        });
+        assert_eq!((result, calls), (5, 5));
+    }
+
+    #[test]
+    fn durations() {
+        assert_eq!(format_duration(Duration::from_nanos(85_321)), "85.3µs");
+        assert_eq!(format_duration(Duration::from_micros(12_345)), "12.35ms");
+        assert_eq!(format_duration(Duration::from_millis(1_250)), "1.25s");
+    }
+}
@@ -0,0 +1,132 @@
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+use crate::config;
+
+/// Name of the stored answers, kept in the target directory
+pub const FILE_NAME: &str = "aoc-cache.tsv";
+
+/// Everything that can change an answer: the solution, its settings, the input and the code
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Key {
+    pub day: usize,
+    pub part: usize,
+    pub variant: String,
+    pub input: u64,
+    pub binary: u64,
+}
+
+/// Answers from previous runs, stored as tab separated lines of the key fields followed by the
+/// answer
+pub struct Cache {
+    path: PathBuf,
+    binary: u64,
+    entries: HashMap<Key, String>,
+}
+
+impl Cache {
+    /// Load the stored answers, discarding any computed by a different build of the program
+    pub fn load() -> Cache {
+        let binary = env::current_exe()
+            .and_then(fs::read)
+            .map(|bytes| hash(&bytes))
+            .unwrap_or_default();
+        let path = config::target_file(FILE_NAME);
+
+        let entries = fs::read_to_string(&path)
+            .unwrap_or_default()
+            .lines()
+            .filter_map(parse_entry)
+            .filter(|(key, _)| key.binary == binary)
+            .collect();
+
+        Cache {
+            path,
+            binary,
+            entries,
+        }
+    }
+
+    pub fn key(&self, day: usize, part: usize, variant: String, input: &str) -> Key {
+        Key {
+            day,
+            part,
+            variant,
+            input: hash(input.as_bytes()),
+            binary: self.binary,
+        }
+    }
+
+    pub fn get(&self, key: &Key) -> Option<&str> {
+        self.entries.get(key).map(String::as_str)
+    }
+
+    pub fn insert(&mut self, key: Key, answer: String) {
+        self.entries.insert(key, answer);
+
+        let mut contents = String::new();
+        for (key, answer) in &self.entries {
+            contents.push_str(&format!(
+                "{}\t{}\t{}\t{:016x}\t{:016x}\t{answer}\n",
+                key.day, key.part, key.variant, key.input, key.binary
+            ));
+        }
+
+        // the cache is only an optimisation, so failing to save it is not worth stopping for
+        if let Err(err) = fs::write(&self.path, contents) {
+            eprintln!(
+                "Unable to save answer cache to {}: {err}",
+                self.path.display()
+            );
+        }
+    }
+}
+
+fn parse_entry(line: &str) -> Option<(Key, String)> {
+    let mut fields = line.splitn(6, '\t');
+    let key = Key {
+        day: fields.next()?.parse().ok()?,
+        part: fields.next()?.parse().ok()?,
+        variant: fields.next()?.to_string(),
+        input: u64::from_str_radix(fields.next()?, 16).ok()?,
+        binary: u64::from_str_radix(fields.next()?, 16).ok()?,
+    };
+    Some((key, fields.next()?.to_string()))
+}
+
+/// 64-bit FNV-1a, which unlike the std hasher is guaranteed to be stable between runs
+fn hash(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, &byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn entries_round_trip() {
+        let key = Key {
+            day: 8,
+            part: 1,
+            variant: "connections=10".to_string(),
+            input: hash(b"162,817,812"),
+            binary: 0xdeadbeef,
+        };
+        let line = format!(
+            "8\t1\tconnections=10\t{:016x}\t00000000deadbeef\t40",
+            key.input
+        );
+        assert_eq!(parse_entry(&line), Some((key, "40".to_string())));
+        assert_eq!(parse_entry("8\t1\tdefault\tnot hex\t0\t40"), None);
+    }
+
+    #[test]
+    fn fnv1a() {
+        assert_eq!(hash(b""), 0xcbf29ce484222325);
+        assert_eq!(hash(b"a"), 0xaf63dc4c8601ec8c);
+    }
+}
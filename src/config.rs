@@ -0,0 +1,508 @@
+use std::env;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::time::Duration;
+
+/// Name of the settings file read from the working directory at startup
+pub const FILE_NAME: &str = "aoc.toml";
+
+static CONFIG: OnceLock<Config> = OnceLock::new();
+
+/// Path of a file kept between runs, in `CARGO_TARGET_DIR` or else `target` under the working
+/// directory, so `cargo clean` clears it
+pub fn target_file(name: &str) -> PathBuf {
+    env::var_os("CARGO_TARGET_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from("target"))
+        .join(name)
+}
+
+/// Where the effective value of a setting came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Source {
+    Default,
+    File,
+    Flag,
+}
+
+#[derive(Debug, Clone)]
+pub struct Setting<T> {
+    pub value: T,
+    pub source: Source,
+}
+
+impl<T> Setting<T> {
+    fn new(value: T) -> Self {
+        Setting {
+            value,
+            source: Source::Default,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Text,
+    Json,
+}
+
+impl fmt::Display for Format {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Format::Text => write!(f, "text"),
+            Format::Json => write!(f, "json"),
+        }
+    }
+}
+
+/// The cells day 4 counts as neighbours of a paper roll
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Neighbourhood {
+    /// Cells within the radius moving only along rows and columns
+    VonNeumann,
+    /// Cells within the radius in both directions, corners included
+    Moore,
+}
+
+impl fmt::Display for Neighbourhood {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Neighbourhood::VonNeumann => write!(f, "von-neumann"),
+            Neighbourhood::Moore => write!(f, "moore"),
+        }
+    }
+}
+
+/// Runner settings, merged from the built-in defaults, `aoc.toml` and command line flags, with
+/// later sources taking precedence
+#[derive(Debug, Clone)]
+pub struct Config {
+    pub path: PathBuf,
+    pub input_dir: Setting<PathBuf>,
+    pub timeout: Setting<Option<Duration>>,
+    pub format: Setting<Format>,
+    pub cache: Setting<bool>,
+    pub iterations: Setting<usize>,
+    pub day1_size: Setting<i64>,
+    pub day1_start: Setting<i64>,
+    pub day1_target: Setting<i64>,
+    pub day2_radix: Setting<u32>,
+    pub day2_lenient: Setting<bool>,
+    pub day4_threshold: Setting<usize>,
+    pub day4_neighbourhood: Setting<Neighbourhood>,
+    pub day4_radius: Setting<usize>,
+    pub day4_wrap: Setting<bool>,
+    pub day8_connections: Setting<usize>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            path: PathBuf::from(FILE_NAME),
+            input_dir: Setting::new(PathBuf::from(concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/src/days/input"
+            ))),
+            timeout: Setting::new(None),
+            format: Setting::new(Format::Text),
+            cache: Setting::new(false),
+            iterations: Setting::new(10),
+            day1_size: Setting::new(100),
+            day1_start: Setting::new(50),
+            day1_target: Setting::new(0),
+            day2_radix: Setting::new(10),
+            day2_lenient: Setting::new(false),
+            day4_threshold: Setting::new(4),
+            day4_neighbourhood: Setting::new(Neighbourhood::Moore),
+            day4_radius: Setting::new(1),
+            day4_wrap: Setting::new(false),
+            day8_connections: Setting::new(1000),
+        }
+    }
+}
+
+impl Config {
+    /// Load the settings file at `path`, if it exists, and then apply each `(key, value)` flag
+    /// on top of it. Flag keys use the same names as the file, e.g. `bench.iterations`.
+    pub fn load(path: &Path, flags: &[(String, String)]) -> Result<Config, String> {
+        let mut config = Config {
+            path: path.to_path_buf(),
+            ..Config::default()
+        };
+
+        match fs::read_to_string(path) {
+            Ok(text) => config
+                .apply_file(&text)
+                .map_err(|err| format!("{}: {err}", path.display()))?,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+            Err(err) => return Err(format!("unable to read {}: {err}", path.display())),
+        }
+
+        for (key, value) in flags {
+            config.set(key, value, Source::Flag)?;
+        }
+
+        Ok(config)
+    }
+
+    fn apply_file(&mut self, text: &str) -> Result<(), String> {
+        let table = text.parse::<toml::Table>().map_err(|err| err.to_string())?;
+
+        for (key, value) in &table {
+            match value {
+                // sections only go one level deep, e.g. [day8] connections = 10
+                toml::Value::Table(section) => {
+                    for (name, value) in section {
+                        self.set(&format!("{key}.{name}"), &value_str(value)?, Source::File)?;
+                    }
+                }
+                value => self.set(key, &value_str(value)?, Source::File)?,
+            }
+        }
+
+        Ok(())
+    }
+
+    fn set(&mut self, key: &str, value: &str, source: Source) -> Result<(), String> {
+        match key {
+            "input_dir" => {
+                self.input_dir = Setting {
+                    value: PathBuf::from(value),
+                    source,
+                }
+            }
+            "timeout" => {
+                let secs = value
+                    .parse::<f64>()
+                    .ok()
+                    .filter(|secs| *secs >= 0.0 && secs.is_finite())
+                    .ok_or_else(|| format!("invalid timeout '{value}', expected seconds"))?;
+                self.timeout = Setting {
+                    // a timeout of zero disables it
+                    value: (secs > 0.0).then(|| Duration::from_secs_f64(secs)),
+                    source,
+                }
+            }
+            "format" => {
+                let format = match value {
+                    "text" => Format::Text,
+                    "json" => Format::Json,
+                    _ => return Err(format!("invalid format '{value}', expected text or json")),
+                };
+                self.format = Setting {
+                    value: format,
+                    source,
+                }
+            }
+            "cache" => {
+                self.cache = Setting {
+                    value: parse_bool(key, value)?,
+                    source,
+                }
+            }
+            "bench.iterations" => {
+                self.iterations = Setting {
+                    value: parse_count(key, value)?,
+                    source,
+                }
+            }
+            "day1.size" => {
+                self.day1_size = Setting {
+                    value: parse_count(key, value)? as i64,
+                    source,
+                }
+            }
+            "day1.start" => {
+                self.day1_start = Setting {
+                    value: parse_position(key, value)?,
+                    source,
+                }
+            }
+            "day1.target" => {
+                self.day1_target = Setting {
+                    value: parse_position(key, value)?,
+                    source,
+                }
+            }
+            "day2.radix" => {
+                let radix = value
+                    .parse::<u32>()
+                    .ok()
+                    .filter(|radix| (2..=36).contains(radix))
+                    .ok_or_else(|| {
+                        format!("invalid value '{value}' for {key}, expected 2 to 36")
+                    })?;
+                self.day2_radix = Setting {
+                    value: radix,
+                    source,
+                }
+            }
+            "day2.lenient" => {
+                self.day2_lenient = Setting {
+                    value: parse_bool(key, value)?,
+                    source,
+                }
+            }
+            "day4.threshold" => {
+                self.day4_threshold = Setting {
+                    value: parse_count(key, value)?,
+                    source,
+                }
+            }
+            "day4.neighbourhood" => {
+                let neighbourhood = match value {
+                    "von-neumann" => Neighbourhood::VonNeumann,
+                    "moore" => Neighbourhood::Moore,
+                    _ => {
+                        return Err(format!(
+                            "invalid value '{value}' for {key}, expected von-neumann or moore"
+                        ));
+                    }
+                };
+                self.day4_neighbourhood = Setting {
+                    value: neighbourhood,
+                    source,
+                }
+            }
+            "day4.radius" => {
+                // a neighbourhood 101 cells across already covers most of a puzzle grid
+                let radius = value
+                    .parse::<usize>()
+                    .ok()
+                    .filter(|radius| (1..=50).contains(radius))
+                    .ok_or_else(|| {
+                        format!("invalid value '{value}' for {key}, expected 1 to 50")
+                    })?;
+                self.day4_radius = Setting {
+                    value: radius,
+                    source,
+                }
+            }
+            "day4.wrap" => {
+                self.day4_wrap = Setting {
+                    value: parse_bool(key, value)?,
+                    source,
+                }
+            }
+            "day8.connections" => {
+                self.day8_connections = Setting {
+                    value: parse_count(key, value)?,
+                    source,
+                }
+            }
+            _ => return Err(format!("unknown setting '{key}'")),
+        }
+        Ok(())
+    }
+
+    /// Read the puzzle input for a day from the input directory
+    pub fn input(&self, day: usize) -> Result<String, String> {
+        let path = self.input_dir.value.join(format!("day{day}.txt"));
+        fs::read_to_string(&path).map_err(|err| {
+            format!(
+                "Unable to read input for day {day} from {}: {err}",
+                path.display()
+            )
+        })
+    }
+
+    /// Describe the settings that change the answer for a part, so answers computed with
+    /// different settings can be told apart
+    pub fn variant(&self, day: usize, part: usize) -> String {
+        match (day, part) {
+            (1, _) => format!(
+                "size={},start={},target={}",
+                self.day1_size.value, self.day1_start.value, self.day1_target.value
+            ),
+            (2, _) => format!(
+                "radix={},lenient={}",
+                self.day2_radix.value, self.day2_lenient.value
+            ),
+            (4, _) => format!(
+                "threshold={},neighbourhood={},radius={},wrap={}",
+                self.day4_threshold.value,
+                self.day4_neighbourhood.value,
+                self.day4_radius.value,
+                self.day4_wrap.value
+            ),
+            (8, 1) => format!("connections={}", self.day8_connections.value),
+            _ => "default".to_string(),
+        }
+    }
+
+    /// Print each setting along with where its value came from
+    pub fn describe(&self) {
+        let file = self.path.display().to_string();
+        let source = |source: Source| match source {
+            Source::Default => "default",
+            Source::File => &file,
+            Source::Flag => "flag",
+        };
+        let timeout = match self.timeout.value {
+            Some(timeout) => format!("{}s", timeout.as_secs_f64()),
+            None => "none".to_string(),
+        };
+
+        let rows = [
+            (
+                "input_dir",
+                self.input_dir.value.display().to_string(),
+                self.input_dir.source,
+            ),
+            ("timeout", timeout, self.timeout.source),
+            ("format", self.format.value.to_string(), self.format.source),
+            ("cache", self.cache.value.to_string(), self.cache.source),
+            (
+                "bench.iterations",
+                self.iterations.value.to_string(),
+                self.iterations.source,
+            ),
+            (
+                "day1.size",
+                self.day1_size.value.to_string(),
+                self.day1_size.source,
+            ),
+            (
+                "day1.start",
+                self.day1_start.value.to_string(),
+                self.day1_start.source,
+            ),
+            (
+                "day1.target",
+                self.day1_target.value.to_string(),
+                self.day1_target.source,
+            ),
+            (
+                "day2.radix",
+                self.day2_radix.value.to_string(),
+                self.day2_radix.source,
+            ),
+            (
+                "day2.lenient",
+                self.day2_lenient.value.to_string(),
+                self.day2_lenient.source,
+            ),
+            (
+                "day4.threshold",
+                self.day4_threshold.value.to_string(),
+                self.day4_threshold.source,
+            ),
+            (
+                "day4.neighbourhood",
+                self.day4_neighbourhood.value.to_string(),
+                self.day4_neighbourhood.source,
+            ),
+            (
+                "day4.radius",
+                self.day4_radius.value.to_string(),
+                self.day4_radius.source,
+            ),
+            (
+                "day4.wrap",
+                self.day4_wrap.value.to_string(),
+                self.day4_wrap.source,
+            ),
+            (
+                "day8.connections",
+                self.day8_connections.value.to_string(),
+                self.day8_connections.source,
+            ),
+        ];
+
+        for (key, value, from) in rows {
+            println!("{key:<18} {value:<24} ({})", source(from));
+        }
+    }
+}
+
+/// Make the loaded settings available to the solutions. Must be called once before any are run.
+pub fn init(config: Config) {
+    CONFIG.set(config).expect("config initialized twice");
+}
+
+pub fn get() -> &'static Config {
+    CONFIG.get_or_init(Config::default)
+}
+
+fn value_str(value: &toml::Value) -> Result<String, String> {
+    match value {
+        toml::Value::String(s) => Ok(s.clone()),
+        toml::Value::Integer(n) => Ok(n.to_string()),
+        toml::Value::Float(n) => Ok(n.to_string()),
+        toml::Value::Boolean(b) => Ok(b.to_string()),
+        _ => Err(format!("unsupported value {value}")),
+    }
+}
+
+fn parse_bool(key: &str, value: &str) -> Result<bool, String> {
+    match value {
+        "true" => Ok(true),
+        "false" => Ok(false),
+        _ => Err(format!(
+            "invalid value '{value}' for {key}, expected true or false"
+        )),
+    }
+}
+
+fn parse_count(key: &str, value: &str) -> Result<usize, String> {
+    match value.parse::<usize>() {
+        Ok(n) if n > 0 => Ok(n),
+        _ => Err(format!(
+            "invalid value '{value}' for {key}, expected a positive integer"
+        )),
+    }
+}
+
+/// Dial positions wrap around, so any non-negative value is accepted
+fn parse_position(key: &str, value: &str) -> Result<i64, String> {
+    match value.parse::<i64>() {
+        Ok(n) if n >= 0 => Ok(n),
+        _ => Err(format!(
+            "invalid value '{value}' for {key}, expected a position of 0 or more"
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn file_then_flags() {
+        let mut config = Config::default();
+        config
+            .apply_file(
+                "timeout = 2.5\nformat = \"json\"\ncache = true\n\n[day8]\nconnections = 10\n",
+            )
+            .unwrap();
+        config.set("day8.connections", "20", Source::Flag).unwrap();
+
+        assert_eq!(config.timeout.value, Some(Duration::from_millis(2500)));
+        assert_eq!(config.timeout.source, Source::File);
+        assert_eq!(config.format.value, Format::Json);
+        assert!(config.cache.value);
+        assert_eq!(config.iterations.source, Source::Default);
+        assert_eq!(config.day8_connections.value, 20);
+        assert_eq!(config.day8_connections.source, Source::Flag);
+    }
+
+    #[test]
+    fn invalid_settings() {
+        let mut config = Config::default();
+        assert!(config.apply_file("colour = \"red\"").is_err());
+        assert!(config.set("bench.iterations", "0", Source::Flag).is_err());
+        assert!(config.set("timeout", "-1", Source::Flag).is_err());
+        assert!(config.set("format", "xml", Source::Flag).is_err());
+        assert!(config.set("day2.radix", "37", Source::Flag).is_err());
+        assert!(config.set("day2.radix", "1", Source::Flag).is_err());
+        assert!(
+            config
+                .set("day4.neighbourhood", "hex", Source::Flag)
+                .is_err()
+        );
+        assert!(config.set("day4.radius", "0", Source::Flag).is_err());
+        assert!(config.set("day4.radius", "51", Source::Flag).is_err());
+    }
+}
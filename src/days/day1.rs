@@ -1,46 +1,111 @@
-const INPUT: &str = include_str!("input/day1.txt");
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::path::PathBuf;
+use std::time::Instant;
 
-pub fn part1() {
-    let result = solution1(INPUT);
-    println!("{result}");
+/// Size, start and target position of the dial in the puzzle
+const SIZE: i64 = 100;
+const START: i64 = 50;
+const TARGET: i64 = 0;
+
+pub fn part1(input: &str) -> Result<String, String> {
+    Ok(solution1(input, configured_dial()).to_string())
 }
 
-pub fn part2() {
-    let result = solution2(INPUT);
-    println!("{result}");
+pub fn part2(input: &str) -> Result<String, String> {
+    Ok(solution2(input, configured_dial()).to_string())
 }
 
-fn solution1(input: &str) -> i64 {
-    let mut position = 50;
-    let mut zero_count = 0;
+/// Read the input a line at a time through the streaming solver, so `bench` can measure it
+pub fn part1_stream(input: &str) -> Result<String, String> {
+    stream(input).map(|dial| dial.landings.to_string())
+}
 
-    for delta in input.lines().map(parse_line) {
-        position = (position + delta).rem_euclid(100);
+pub fn part2_stream(input: &str) -> Result<String, String> {
+    stream(input).map(|dial| dial.crossings.to_string())
+}
+
+fn stream(input: &str) -> Result<Dial, String> {
+    solve_reader(input.as_bytes(), configured_dial())
+        .map(|(dial, _)| dial)
+        .map_err(|err| err.to_string())
+}
+
+fn configured_dial() -> Dial {
+    let config = crate::config::get();
+    Dial::new(
+        config.day1_size.value,
+        config.day1_start.value,
+        config.day1_target.value,
+    )
+}
+
+/// A dial with positions `0..size`, counting how often it points at the target position
+#[derive(Debug, Clone)]
+struct Dial {
+    size: i64,
+    position: i64,
+    target: i64,
+    /// Rotations that finished on the target
+    landings: i64,
+    /// Times the target was passed or landed on during a rotation
+    crossings: i64,
+}
 
-        if position == 0 {
-            zero_count += 1;
+impl Default for Dial {
+    fn default() -> Self {
+        Dial::new(SIZE, START, TARGET)
+    }
+}
+
+impl Dial {
+    fn new(size: i64, start: i64, target: i64) -> Self {
+        assert!(size > 0, "dial must have at least one position");
+        Dial {
+            size,
+            position: start.rem_euclid(size),
+            target: target.rem_euclid(size),
+            landings: 0,
+            crossings: 0,
         }
     }
 
-    zero_count
+    /// Turn the dial by `delta` clicks, right for positive, and return the number of times it
+    /// pointed at the target along the way
+    fn rotate(&mut self, delta: i64) -> i64 {
+        // measure from the target so it's the zero that apply_rotation counts
+        let relative = (self.position - self.target).rem_euclid(self.size);
+        let (new_relative, crossings) = apply_rotation(relative, delta, self.size);
+
+        self.position = (new_relative + self.target).rem_euclid(self.size);
+        if new_relative == 0 {
+            self.landings += 1;
+        }
+        self.crossings += crossings;
+
+        crossings
+    }
 }
 
-fn solution2(input: &str) -> i64 {
-    let mut position = 50;
-    let mut zero_count = 0;
+fn solution1(input: &str, mut dial: Dial) -> i64 {
+    for delta in input.lines().map(parse_line) {
+        dial.rotate(delta);
+    }
+
+    dial.landings
+}
 
+fn solution2(input: &str, mut dial: Dial) -> i64 {
     for delta in input.lines().map(parse_line) {
-        let (new_pos, zeroes) = apply_rotation(position, delta);
-        position = new_pos;
-        zero_count += zeroes;
+        dial.rotate(delta);
     }
 
-    zero_count
+    dial.crossings
 }
 
-fn apply_rotation(start_pos: i64, delta: i64) -> (i64, i64) {
-    let new_pos = (start_pos + delta).rem_euclid(100);
-    let mut zeroes = (start_pos + delta).div_euclid(100).abs();
+fn apply_rotation(start_pos: i64, delta: i64, size: i64) -> (i64, i64) {
+    let new_pos = (start_pos + delta).rem_euclid(size);
+    let mut zeroes = (start_pos + delta).div_euclid(size).abs();
 
     // Only count landing on zero when going left as going right, the div_euclid picks it up
     if new_pos == 0 && delta < 0 {
@@ -52,22 +117,404 @@ fn apply_rotation(start_pos: i64, delta: i64) -> (i64, i64) {
         zeroes -= 1;
     }
 
-    return (new_pos, zeroes);
+    (new_pos, zeroes)
+}
+
+/// One rotation of the dial, with positions given as absolute dial positions
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Step {
+    delta: i64,
+    start: i64,
+    end: i64,
+    crossings: i64,
+}
+
+fn trace(input: &str, mut dial: Dial) -> Vec<Step> {
+    input
+        .lines()
+        .map(parse_line)
+        .map(|delta| {
+            let start = dial.position;
+            let crossings = dial.rotate(delta);
+            Step {
+                delta,
+                start,
+                end: dial.position,
+                crossings,
+            }
+        })
+        .collect()
+}
+
+/// The same trace as `trace`, but turning the dial one click at a time, to check it against
+fn brute_force_trace(input: &str, dial: Dial) -> Vec<Step> {
+    let mut position = dial.position;
+
+    input
+        .lines()
+        .map(parse_line)
+        .map(|delta| {
+            let start = position;
+            let mut crossings = 0;
+            for _ in 0..delta.abs() {
+                position = (position + delta.signum()).rem_euclid(dial.size);
+                if position == dial.target {
+                    crossings += 1;
+                }
+            }
+            Step {
+                delta,
+                start,
+                end: position,
+                crossings,
+            }
+        })
+        .collect()
+}
+
+/// Print each rotation with the positions and target crossings. Pass `--csv` for CSV instead of
+/// a table and `--brute-force` to trace by simulating every click, so the two can be diffed.
+pub fn trace_tool(args: &[String]) -> Result<(), String> {
+    let mut csv = false;
+    let mut brute_force = false;
+    for arg in args {
+        match arg.as_str() {
+            "--csv" => csv = true,
+            "--brute-force" => brute_force = true,
+            _ => return Err(format!("unknown option '{arg}'")),
+        }
+    }
+    let input = crate::config::get().input(1)?;
+
+    let steps = if brute_force {
+        brute_force_trace(&input, configured_dial())
+    } else {
+        trace(&input, configured_dial())
+    };
+
+    if csv {
+        println!("line,input,delta,start,end,crossings,total");
+    } else {
+        println!(
+            "{:>6}  {:<8} {:>8} {:>6} {:>6} {:>9} {:>8}",
+            "line", "input", "delta", "start", "end", "crossings", "total"
+        );
+    }
+
+    let mut total = 0;
+    for (n, (line, step)) in input.lines().zip(steps).enumerate() {
+        total += step.crossings;
+        let Step {
+            delta,
+            start,
+            end,
+            crossings,
+        } = step;
+        if csv {
+            println!("{},{line},{delta},{start},{end},{crossings},{total}", n + 1);
+        } else {
+            println!(
+                "{:>6}  {line:<8} {delta:>8} {start:>6} {end:>6} {crossings:>9} {total:>8}",
+                n + 1
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// How often each dial position was pointed at over the whole input
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Visits {
+    /// Rotations that finished on each position after turning at least one click
+    landings: Vec<u64>,
+    /// Rotations of zero clicks, which finish where they started without pointing anywhere new
+    stays: Vec<u64>,
+    /// Times each position was passed over partway through a rotation
+    passes: Vec<u64>,
+    /// Total clicks turned in either direction
+    distance: u64,
+}
+
+impl Visits {
+    /// Every time a position was pointed at, whether passing or landing. As with the crossings
+    /// counted by `apply_rotation`, the position a rotation starts from isn't counted.
+    fn total(&self, position: usize) -> u64 {
+        self.landings[position] + self.passes[position]
+    }
+
+    /// Every rotation that finished on a position, as counted by part 1
+    fn finishes(&self, position: usize) -> u64 {
+        self.landings[position] + self.stays[position]
+    }
+
+    fn most_visited(&self) -> usize {
+        // ties go to the lowest position
+        (0..self.landings.len())
+            .rev()
+            .max_by_key(|&position| self.total(position))
+            .unwrap()
+    }
+
+    fn least_visited(&self) -> usize {
+        (0..self.landings.len())
+            .min_by_key(|&position| self.total(position))
+            .unwrap()
+    }
+}
+
+/// Count visits to every position without turning the dial click by click. Like
+/// `apply_rotation`, each rotation is split into whole turns, which visit every position, and
+/// the remaining arc, which is added as a range to a difference array so each line costs O(1).
+fn visits(input: &str, mut dial: Dial) -> Visits {
+    let size = dial.size as usize;
+    let mut landings = vec![0; size];
+    let mut stays = vec![0; size];
+    let mut arcs = vec![0i64; size + 1];
+    let mut turns = 0;
+    let mut distance = 0;
+
+    for delta in input.lines().map(parse_line) {
+        let start = dial.position;
+        dial.rotate(delta);
+        if delta == 0 {
+            stays[dial.position as usize] += 1;
+        } else {
+            landings[dial.position as usize] += 1;
+        }
+
+        let clicks = delta.unsigned_abs();
+        distance += clicks;
+        turns += clicks / size as u64;
+
+        // the arc covers the positions one past the start up to the end, in the direction turned
+        let arc = (clicks % size as u64) as usize;
+        if arc > 0 {
+            let first = if delta > 0 {
+                (start + 1).rem_euclid(dial.size)
+            } else {
+                (start - arc as i64).rem_euclid(dial.size)
+            } as usize;
+            let last = first + arc;
+            arcs[first] += 1;
+            if last <= size {
+                arcs[last] -= 1;
+            } else {
+                arcs[size] -= 1;
+                arcs[0] += 1;
+                arcs[last - size] -= 1;
+            }
+        }
+    }
+
+    // every visit is either the landing that ends a rotation or a pass, and the rotations that
+    // stay put visit nothing
+    let mut covered = 0;
+    let passes = (0..size)
+        .map(|position| {
+            covered += arcs[position];
+            covered as u64 + turns - landings[position]
+        })
+        .collect();
+
+    Visits {
+        landings,
+        stays,
+        passes,
+        distance,
+    }
+}
+
+/// Print the total distance turned and the most and least visited positions. Pass `--histogram`
+/// to also list the landings and passes for every position.
+pub fn visits_tool(args: &[String]) -> Result<(), String> {
+    let mut histogram = false;
+    for arg in args {
+        match arg.as_str() {
+            "--histogram" => histogram = true,
+            _ => return Err(format!("unknown option '{arg}'")),
+        }
+    }
+    let input = crate::config::get().input(1)?;
+
+    let visits = visits(&input, configured_dial());
+    let most = visits.most_visited();
+    let least = visits.least_visited();
+    println!("distance turned: {} clicks", visits.distance);
+    println!("most visited:    {most} ({} times)", visits.total(most));
+    println!("least visited:   {least} ({} times)", visits.total(least));
+
+    if histogram {
+        println!();
+        println!(
+            "{:>8} {:>10} {:>10} {:>10}",
+            "position", "landings", "passes", "total"
+        );
+        for position in 0..visits.landings.len() {
+            println!(
+                "{position:>8} {:>10} {:>10} {:>10}",
+                visits.landings[position],
+                visits.passes[position],
+                visits.total(position)
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// The part 1 and part 2 counts for every start position of the dial, indexed by start
+fn counts_by_start(input: &str, dial: &Dial) -> Vec<[u64; 2]> {
+    // moving the start moves every position the dial points at by the same amount, so starting
+    // at s hits the target whenever starting at 0 hits target - s, and one pass covers them all
+    let visits = visits(input, Dial::new(dial.size, 0, dial.target));
+
+    (0..dial.size)
+        .map(|start| {
+            let position = (dial.target - start).rem_euclid(dial.size) as usize;
+            [visits.finishes(position), visits.total(position)]
+        })
+        .collect()
+}
+
+/// Print which start positions give the fewest and most target hits for each part. Pass `--all`
+/// to list the counts for every start.
+pub fn starts_tool(args: &[String]) -> Result<(), String> {
+    let mut all = false;
+    for arg in args {
+        match arg.as_str() {
+            "--all" => all = true,
+            _ => return Err(format!("unknown option '{arg}'")),
+        }
+    }
+    let input = crate::config::get().input(1)?;
+
+    let dial = configured_dial();
+    let counts = counts_by_start(&input, &dial);
+
+    for part in 1..=2 {
+        let min = counts.iter().map(|count| count[part - 1]).min().unwrap();
+        let max = counts.iter().map(|count| count[part - 1]).max().unwrap();
+        // list runs of consecutive starts as ranges, e.g. 0-17, 19
+        let starts_with = |value| {
+            let starts: Vec<_> = (0..counts.len())
+                .filter(|&start| counts[start][part - 1] == value)
+                .collect();
+            let runs: Vec<_> = starts
+                .chunk_by(|a, b| a + 1 == *b)
+                .map(|run| match run {
+                    [start] => start.to_string(),
+                    [first, .., last] => format!("{first}-{last}"),
+                    [] => unreachable!(),
+                })
+                .collect();
+            runs.join(", ")
+        };
+        println!("part {part}: min {min} starting at {}", starts_with(min));
+        println!("part {part}: max {max} starting at {}", starts_with(max));
+    }
+
+    if all {
+        println!();
+        println!("{:>6} {:>8} {:>8}", "start", "part 1", "part 2");
+        for (start, [part1, part2]) in counts.iter().enumerate() {
+            println!("{start:>6} {part1:>8} {part2:>8}");
+        }
+    }
+
+    Ok(())
+}
+
+/// Run the dial over rotations read a line at a time, so memory use stays the same however long
+/// the input is. Returns the dial after the last rotation and the number of lines read.
+fn solve_reader(mut reader: impl BufRead, mut dial: Dial) -> io::Result<(Dial, u64)> {
+    let mut line = String::new();
+    let mut lines = 0;
+
+    loop {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 {
+            break;
+        }
+        lines += 1;
+
+        let rotation = line.trim_end_matches(['\n', '\r']);
+        let delta = parse_rotation(rotation).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("invalid rotation '{rotation}' on line {lines}"),
+            )
+        })?;
+        dial.rotate(delta);
+    }
+
+    Ok((dial, lines))
+}
+
+/// Solve both parts from a rotation log of any size without loading it into memory. Reads the
+/// day's input unless given a path, or `-` for stdin. Pass `--bench` to report the throughput.
+pub fn stream_tool(args: &[String]) -> Result<(), String> {
+    let mut path = None;
+    let mut bench = false;
+    for arg in args {
+        match arg.as_str() {
+            "--bench" => bench = true,
+            _ if path.is_none() && (arg == "-" || !arg.starts_with('-')) => path = Some(arg),
+            _ => return Err(format!("unknown option '{arg}'")),
+        }
+    }
+
+    let now = Instant::now();
+    let result = match path.map(String::as_str) {
+        Some("-") => solve_reader(io::stdin().lock(), configured_dial()),
+        path => {
+            let path = path
+                .map(PathBuf::from)
+                .unwrap_or_else(|| crate::config::get().input_dir.value.join("day1.txt"));
+            let file = File::open(&path)
+                .map_err(|err| format!("Unable to open {}: {err}", path.display()))?;
+            solve_reader(BufReader::with_capacity(1 << 16, file), configured_dial())
+        }
+    };
+    let (dial, lines) = result.map_err(|err| err.to_string())?;
+    let elapsed = now.elapsed();
+
+    println!("part 1: {}", dial.landings);
+    println!("part 2: {}", dial.crossings);
+    if bench {
+        println!(
+            "{lines} lines in {} ({:.0} lines/sec)",
+            crate::bench::format_duration(elapsed),
+            lines as f64 / elapsed.as_secs_f64()
+        );
+    }
+
+    Ok(())
 }
 
 fn parse_line(input: &str) -> i64 {
-    // Parse direction and value
-    let (dir, dist) = input.split_at(1);
-    let dist = dist.parse::<i64>().expect("puzzle input guarantees an int");
-    dist * match dir {
-        "L" => -1,
-        "R" => 1,
-        _ => panic!("invalid puzzle input {input}"),
+    parse_rotation(input).unwrap_or_else(|| panic!("invalid puzzle input {input}"))
+}
+
+/// Parse a direction and distance into clicks, negative for left
+fn parse_rotation(input: &str) -> Option<i64> {
+    let (dir, dist) = input.split_at_checked(1)?;
+    // the distance is plain digits, so a sign can't reverse the direction
+    if !dist.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let dist = dist.parse::<i64>().ok()?;
+    match dir {
+        "L" => Some(-dist),
+        "R" => Some(dist),
+        _ => None,
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use super::*;
+
     const INPUT: &str = "L68
 L30
 R48
@@ -79,35 +526,189 @@ L99
 R14
 L82";
 
+    /// Rotations that don't move, including onto the target, mixed in with ones that do
+    const STAYS: &str = "R0\nL50\nR0\nL0\nR100\nL0\nR3\nL0";
+
     #[test]
     fn part1() {
-        assert_eq!(super::solution1(INPUT), 3);
+        assert_eq!(solution1(INPUT, Dial::default()), 3);
     }
 
     #[test]
     fn part2() {
-        assert_eq!(super::solution2(INPUT), 6);
+        assert_eq!(solution2(INPUT, Dial::default()), 6);
     }
 
     #[test]
     fn examples() {
-        assert_eq!(super::apply_rotation(50, -68), (82, 1));
-        assert_eq!(super::apply_rotation(82, -30), (52, 0));
-        assert_eq!(super::apply_rotation(52, 48), (0, 1));
-        assert_eq!(super::apply_rotation(0, -5), (95, 0));
-        assert_eq!(super::apply_rotation(95, 60), (55, 1));
-        assert_eq!(super::apply_rotation(55, -55), (0, 1));
-        assert_eq!(super::apply_rotation(0, -1), (99, 0));
-        assert_eq!(super::apply_rotation(99, -99), (0, 1));
-        assert_eq!(super::apply_rotation(0, 14), (14, 0));
-        assert_eq!(super::apply_rotation(14, -82), (32, 1));
+        assert_eq!(apply_rotation(50, -68, SIZE), (82, 1));
+        assert_eq!(apply_rotation(82, -30, SIZE), (52, 0));
+        assert_eq!(apply_rotation(52, 48, SIZE), (0, 1));
+        assert_eq!(apply_rotation(0, -5, SIZE), (95, 0));
+        assert_eq!(apply_rotation(95, 60, SIZE), (55, 1));
+        assert_eq!(apply_rotation(55, -55, SIZE), (0, 1));
+        assert_eq!(apply_rotation(0, -1, SIZE), (99, 0));
+        assert_eq!(apply_rotation(99, -99, SIZE), (0, 1));
+        assert_eq!(apply_rotation(0, 14, SIZE), (14, 0));
+        assert_eq!(apply_rotation(14, -82, SIZE), (32, 1));
     }
 
     #[test]
     fn large_turns() {
-        assert_eq!(super::apply_rotation(50, -200), (50, 2));
-        assert_eq!(super::apply_rotation(50, 200), (50, 2));
-        assert_eq!(super::apply_rotation(0, 200), (0, 2));
-        assert_eq!(super::apply_rotation(0, -200), (0, 2));
+        assert_eq!(apply_rotation(50, -200, SIZE), (50, 2));
+        assert_eq!(apply_rotation(50, 200, SIZE), (50, 2));
+        assert_eq!(apply_rotation(0, 200, SIZE), (0, 2));
+        assert_eq!(apply_rotation(0, -200, SIZE), (0, 2));
+    }
+
+    /// Turn the dial one click at a time, counting every time it points at the target
+    fn simulate(size: i64, start: i64, target: i64, deltas: &[i64]) -> (i64, i64) {
+        let (mut position, mut landings, mut crossings) = (start, 0, 0);
+        for &delta in deltas {
+            for _ in 0..delta.abs() {
+                position = (position + delta.signum()).rem_euclid(size);
+                if position == target {
+                    crossings += 1;
+                }
+            }
+            if position == target {
+                landings += 1;
+            }
+        }
+        (landings, crossings)
+    }
+
+    #[test]
+    fn dial_sizes() {
+        // deterministic pseudo-random rotations of up to three turns of the largest dial
+        let mut lcg = crate::days::random::Lcg::new(12345);
+        let deltas: Vec<i64> = (0..200).map(|_| lcg.below(601) as i64 - 300).collect();
+
+        for size in [1, 2, 3, 7, 10, 100] {
+            for target in [0, size / 2, size - 1] {
+                let start = size / 3;
+                let mut dial = Dial::new(size, start, target);
+                for &delta in &deltas {
+                    dial.rotate(delta);
+                }
+                assert_eq!(
+                    (dial.landings, dial.crossings),
+                    simulate(size, start, target, &deltas),
+                    "size {size}, target {target}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn traces_match_brute_force() {
+        let steps = trace(INPUT, Dial::default());
+        assert_eq!(steps, brute_force_trace(INPUT, Dial::default()));
+        assert_eq!(
+            steps[0],
+            Step {
+                delta: -68,
+                start: 50,
+                end: 82,
+                crossings: 1
+            }
+        );
+        assert_eq!(steps.iter().map(|step| step.crossings).sum::<i64>(), 6);
+
+        let dial = Dial::new(7, 3, 4);
+        assert_eq!(trace(INPUT, dial.clone()), brute_force_trace(INPUT, dial));
+    }
+
+    #[test]
+    fn visit_counts() {
+        let visits = visits(INPUT, Dial::default());
+        assert_eq!(
+            visits.distance,
+            68 + 30 + 48 + 5 + 60 + 55 + 1 + 99 + 14 + 82
+        );
+        assert_eq!(visits.landings[0], 3);
+        assert_eq!(visits.total(0), 6);
+        assert_eq!(visits.landings.iter().sum::<u64>(), 10);
+        assert_eq!(
+            visits.landings.iter().chain(&visits.passes).sum::<u64>(),
+            visits.distance
+        );
+
+        // rotations of zero clicks finish somewhere without visiting it
+        let visits = super::visits("R0\nL5", Dial::default());
+        assert_eq!(
+            (visits.stays[50], visits.landings[50], visits.total(50)),
+            (1, 0, 0)
+        );
+        assert_eq!((visits.finishes(45), visits.total(45)), (1, 1));
+
+        // compare every position with a click by click count
+        for input in [INPUT, STAYS] {
+            for dial in [Dial::default(), Dial::new(7, 3, 0), Dial::new(1, 0, 0)] {
+                let visits = super::visits(input, dial.clone());
+                let mut position = dial.position;
+                let mut expected = vec![0; dial.size as usize];
+                for delta in input.lines().map(parse_line) {
+                    for _ in 0..delta.abs() {
+                        position = (position + delta.signum()).rem_euclid(dial.size);
+                        expected[position as usize] += 1;
+                    }
+                }
+                let totals: Vec<_> = (0..expected.len()).map(|p| visits.total(p)).collect();
+                assert_eq!(totals, expected, "size {}", dial.size);
+            }
+        }
+    }
+
+    #[test]
+    fn every_start() {
+        for input in [INPUT, STAYS] {
+            for (size, target) in [(SIZE, TARGET), (7, 4), (1, 0)] {
+                let counts = counts_by_start(input, &Dial::new(size, 0, target));
+                assert_eq!(counts.len(), size as usize);
+
+                for (start, &[part1, part2]) in counts.iter().enumerate() {
+                    let dial = Dial::new(size, start as i64, target);
+                    assert_eq!(part1 as i64, solution1(input, dial.clone()), "size {size}");
+                    assert_eq!(part2 as i64, solution2(input, dial), "size {size}");
+                }
+            }
+        }
+        assert_eq!(
+            counts_by_start(INPUT, &Dial::default())[START as usize],
+            [3, 6]
+        );
+    }
+
+    #[test]
+    fn streaming() {
+        let (dial, lines) = solve_reader(INPUT.as_bytes(), Dial::default()).unwrap();
+        assert_eq!((dial.landings, dial.crossings, lines), (3, 6, 10));
+
+        let crlf = INPUT.replace('\n', "\r\n") + "\r\n";
+        let (dial, lines) = solve_reader(crlf.as_bytes(), Dial::default()).unwrap();
+        assert_eq!((dial.landings, dial.crossings, lines), (3, 6, 10));
+
+        let err = solve_reader("L68\nX30\n".as_bytes(), Dial::default()).unwrap_err();
+        assert_eq!(err.to_string(), "invalid rotation 'X30' on line 2");
+        assert!(solve_reader("L68\n\nR5".as_bytes(), Dial::default()).is_err());
+        for signed in ["R-5", "R+5", "L-5"] {
+            assert!(solve_reader(signed.as_bytes(), Dial::default()).is_err());
+        }
+        assert_eq!(stream(INPUT).unwrap().landings, 3);
+        assert_eq!(
+            super::part1_stream("R-5").unwrap_err(),
+            "invalid rotation 'R-5' on line 1"
+        );
+    }
+
+    #[test]
+    fn rotate_returns_crossings() {
+        let mut dial = Dial::new(10, 0, 5);
+        assert_eq!(dial.rotate(5), 1);
+        assert_eq!(dial.rotate(-25), 2);
+        assert_eq!(dial.rotate(3), 0);
+        assert_eq!(dial.position, 3);
+        assert_eq!((dial.landings, dial.crossings), (1, 3));
     }
 }
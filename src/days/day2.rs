@@ -1,83 +1,544 @@
-const INPUT: &str = include_str!("input/day2.txt");
-
 use regex::Regex;
+use std::collections::BTreeMap;
 use std::sync::LazyLock;
 
-pub fn part1() {
-    println!("{}", solution1(INPUT));
+use super::intervals::{Merged, merge};
+
+/// How the ranges are read and which digits the patterns are looked for in
+#[derive(Debug, Clone, Copy)]
+struct Settings {
+    radix: u32,
+    /// Pick out anything that looks like a range and ignore the rest, rather than rejecting the
+    /// input
+    lenient: bool,
+}
+
+/// The puzzle looks for patterns in the decimal digits of each ID
+const PUZZLE: Settings = Settings {
+    radix: 10,
+    lenient: false,
+};
+
+pub fn part1(input: &str) -> Result<String, String> {
+    solution1(input, configured()).map(|sum| sum.to_string())
+}
+
+pub fn part2(input: &str) -> Result<String, String> {
+    solution2(input, configured()).map(|sum| sum.to_string())
+}
+
+/// Check every number in each range rather than generating the repeated ones
+pub fn part1_scan(input: &str) -> Result<String, String> {
+    let Settings { radix, lenient } = configured();
+    let ranges = distinct_ranges(input, lenient)?;
+    let ids = ranges
+        .into_iter()
+        .flat_map(|range| find_doubles(range, radix));
+    checked_sum(ids)
+        .map(|sum| sum.to_string())
+        .ok_or_else(|| "the sum overflows a u128".to_string())
+}
+
+pub fn part2_scan(input: &str) -> Result<String, String> {
+    let Settings { radix, lenient } = configured();
+    let ranges = distinct_ranges(input, lenient)?;
+    let ids = ranges
+        .into_iter()
+        .flat_map(|range| find_repeats(range, radix));
+    checked_sum(ids)
+        .map(|sum| sum.to_string())
+        .ok_or_else(|| "the sum overflows a u128".to_string())
+}
+
+fn configured() -> Settings {
+    let config = crate::config::get();
+    Settings {
+        radix: config.day2_radix.value,
+        lenient: config.day2_lenient.value,
+    }
+}
+
+fn checked_sum(nums: impl IntoIterator<Item = u128>) -> Option<u128> {
+    nums.into_iter()
+        .try_fold(0u128, |sum, num| sum.checked_add(num))
+}
+
+fn solution1(input: &str, Settings { radix, lenient }: Settings) -> Result<u128, String> {
+    let sums = distinct_ranges(input, lenient)?
+        .into_iter()
+        .map(|range| sum_doubles(range, radix).ok_or_else(|| overflow(range)))
+        .collect::<Result<Vec<_>, _>>()?;
+    checked_sum(sums).ok_or_else(|| "the total overflows a u128".to_string())
+}
+
+fn solution2(input: &str, Settings { radix, lenient }: Settings) -> Result<u128, String> {
+    let sums = distinct_ranges(input, lenient)?
+        .into_iter()
+        .map(|range| sum_repeats(range, radix).ok_or_else(|| overflow(range)))
+        .collect::<Result<Vec<_>, _>>()?;
+    checked_sum(sums).ok_or_else(|| "the total overflows a u128".to_string())
 }
 
-pub fn part2() {
-    println!("{}", solution2(INPUT));
+fn overflow((start, end): (u128, u128)) -> String {
+    format!("the sum of the repeated IDs in {start}-{end} overflows a u128")
 }
 
-fn solution1(input: &str) -> u64 {
-    parse_ranges(input).flat_map(find_doubles).sum()
+/// Parse the ranges and combine any that overlap, so no ID is counted twice
+fn distinct_ranges(input: &str, lenient: bool) -> Result<Vec<(u128, u128)>, String> {
+    merged_ranges(input, lenient).map(distinct)
 }
 
-fn solution2(input: &str) -> u64 {
-    parse_ranges(input).flat_map(find_repeats).sum()
+fn distinct(merged: Merged<u128>) -> Vec<(u128, u128)> {
+    merged
+        .ranges
+        .into_iter()
+        .map(|range| (*range.start(), *range.end()))
+        .collect()
 }
 
-fn parse_ranges(input: &str) -> impl Iterator<Item = (u64, u64)> + '_ {
+/// Parse the ranges and merge them, keeping track of which overlapped
+fn merged_ranges(input: &str, lenient: bool) -> Result<Merged<u128>, String> {
+    let ranges = if lenient {
+        parse_lenient(input)?
+    } else {
+        parse_ranges(input)?
+    };
+    Ok(merge(ranges.into_iter().map(|(start, end)| start..=end)))
+}
+
+/// Point out ranges that overlap, whose shared IDs are only counted once
+pub fn warnings(input: &str) -> Vec<String> {
+    // invalid input is reported when the parts fail
+    let Ok(merged) = merged_ranges(input, configured().lenient) else {
+        return Vec::new();
+    };
+    overlap_warning(&merged).into_iter().collect()
+}
+
+fn overlap_warning(merged: &Merged<u128>) -> Option<String> {
+    if merged.overlaps.is_empty() {
+        return None;
+    }
+    let list: Vec<_> = merged
+        .overlaps
+        .iter()
+        .map(|(earlier, later)| {
+            let (a, b) = (earlier.start(), earlier.end());
+            let (c, d) = (later.start(), later.end());
+            format!("{a}-{b} and {c}-{d}")
+        })
+        .collect();
+    Some(format!(
+        "overlapping day 2 ranges, counting their IDs once: {}",
+        list.join(", ")
+    ))
+}
+
+/// Parse comma separated ranges like `11-22`, allowing whitespace around each one. Every bad
+/// range is reported with the byte offset it starts at.
+fn parse_ranges(input: &str) -> Result<Vec<(u128, u128)>, String> {
+    let mut ranges = Vec::new();
+    let mut errors = Vec::new();
+    let mut offset = 0;
+
+    for token in input.trim_end().split(',') {
+        let start = offset + token.len() - token.trim_start().len();
+        offset += token.len() + 1;
+        let token = token.trim();
+
+        match parse_range(token) {
+            Ok((low, high)) if low > high => {
+                errors.push(format!("reversed range '{token}' at byte {start}"))
+            }
+            Ok(range) => ranges.push(range),
+            Err(err) => errors.push(format!("{err} at byte {start}")),
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(ranges)
+    } else {
+        Err(errors.join("; "))
+    }
+}
+
+fn parse_range(token: &str) -> Result<(u128, u128), String> {
+    if token.is_empty() {
+        return Err("missing range".to_string());
+    }
+    let bound = |bound: &str| {
+        if bound.is_empty() || !bound.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(format!("invalid range '{token}'"));
+        }
+        bound
+            .parse::<u128>()
+            .map_err(|_| format!("'{bound}' is too large for a u128"))
+    };
+
+    let (start, end) = token
+        .split_once('-')
+        .ok_or_else(|| format!("invalid range '{token}'"))?;
+    Ok((bound(start)?, bound(end)?))
+}
+
+/// Pick out everything that looks like a range, skipping anything else
+fn parse_lenient(input: &str) -> Result<Vec<(u128, u128)>, String> {
     static RANGES: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"([0-9]+)-([0-9]+)").unwrap());
 
-    RANGES.captures_iter(input).map(|caps| {
-        let start = caps[1].parse::<u64>().unwrap();
-        let end = caps[2].parse::<u64>().unwrap();
-        (start, end)
-    })
+    let parse = |bound: &str| {
+        bound
+            .parse::<u128>()
+            .map_err(|_| format!("'{bound}' is too large for a u128"))
+    };
+    RANGES
+        .captures_iter(input)
+        .map(|caps| Ok((parse(&caps[1])?, parse(&caps[2])?)))
+        .collect()
 }
 
-fn find_doubles((start, end): (u64, u64)) -> impl Iterator<Item = u64> {
-    use ilog::IntLog;
+/// Number of digits needed to write `num` in the radix
+fn digit_count(num: u128, radix: u32) -> u32 {
+    num.checked_ilog(radix as u128).unwrap_or(0) + 1
+}
 
-    (start..=end).filter_map(|num| {
-        let digits = (num.log10() + 1) as u32;
+fn find_doubles((start, end): (u128, u128), radix: u32) -> impl Iterator<Item = u128> {
+    (start..=end).filter(move |&num| {
+        let digits = digit_count(num, radix);
         // Skip odd numbers
-        if digits % 2 > 0 {
-            return None;
+        if !digits.is_multiple_of(2) {
+            return false;
         };
 
         // Collect the right half using mod and compare with the left half
-        let right = num % 10u64.pow(digits / 2);
-        let left = (num - right) / 10u64.pow(digits / 2);
-        if right == left {
-            return Some(num);
+        let half = (radix as u128).pow(digits / 2);
+        let right = num % half;
+        let left = (num - right) / half;
+        right == left
+    })
+}
+
+fn find_repeats((start, end): (u128, u128), radix: u32) -> impl Iterator<Item = u128> {
+    (start..=end).filter(move |&num| classify(num, radix).is_some())
+}
+
+/// The shortest pattern that repeats to make a number, e.g. 123123123 is 123 three times
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Repetition {
+    pattern: u128,
+    length: u32,
+    repeats: u32,
+}
+
+/// Find the shortest pattern repeated at least twice to make `num` when written in the radix, if
+/// there is one
+fn classify(num: u128, radix: u32) -> Option<Repetition> {
+    let digits = digit_count(num, radix);
+    let radix = radix as u128;
+
+    // Check all possible pattern lengths
+    'outer: for length in 1..=(digits / 2) {
+        // Skip if can't divide evenly
+        if !digits.is_multiple_of(length) {
+            continue;
         }
 
-        None
+        // Truncate off the right part and compare it to the pattern and repeat until all have been compared
+        let pattern = num % radix.pow(length);
+        let mut remainder = (num - pattern) / radix.pow(length);
+        while remainder > 0 {
+            let right = remainder % radix.pow(length);
+            if right != pattern {
+                continue 'outer;
+            }
+            remainder = (remainder - right) / radix.pow(length);
+        }
+        return Some(Repetition {
+            pattern,
+            length,
+            repeats: digits / length,
+        });
+    }
+    None
+}
+
+/// Split a range into the parts with the same number of digits, as `(digits, start, end)`
+fn by_digit_length(
+    (start, end): (u128, u128),
+    radix: u32,
+) -> impl Iterator<Item = (u32, u128, u128)> {
+    let radix = radix as u128;
+
+    (1..=digit_count(u128::MAX, radix as u32)).filter_map(move |digits| {
+        let low = start.max(radix.pow(digits - 1));
+        // the longest numbers go up to the largest u128
+        let high = end.min(
+            radix
+                .checked_pow(digits)
+                .map_or(u128::MAX, |limit| limit - 1),
+        );
+        (low <= high).then_some((digits, low, high))
     })
 }
 
-fn find_repeats((start, end): (u64, u64)) -> impl Iterator<Item = u64> {
-    use ilog::IntLog;
+/// Find the `length` digit patterns that repeat to make a `digits` long number in `low..=high`.
+/// Every such number is the pattern times a multiplier of ones separated by `length - 1` zeroes,
+/// e.g. 123123 = 123 * 1001, so the patterns in range form a run of integers. Returns the
+/// multiplier and the first and last pattern.
+fn pattern_bounds(
+    low: u128,
+    high: u128,
+    digits: u32,
+    length: u32,
+    radix: u32,
+) -> Option<(u128, u128, u128)> {
+    let radix = radix as u128;
+    // built up one repeat at a time, as radix^digits may not fit in a u128
+    let multiplier =
+        (0..digits / length).fold(0, |multiplier, _| multiplier * radix.pow(length) + 1);
 
-    (start..=end).filter_map(|num| {
-        let digits = (num.log10() + 1) as u32;
+    // the pattern can't start with a zero
+    let first = low.div_ceil(multiplier).max(radix.pow(length - 1));
+    let last = (high / multiplier).min(radix.pow(length) - 1);
+    (first <= last).then_some((multiplier, first, last))
+}
 
-        // Check all possible pattern lengths
-        'outer: for length in 1..=(digits / 2) {
-            // Skip if can't divide evenly
-            if digits % length > 0 {
-                continue;
+/// Count and sum the numbers in `low..=high`, all `digits` long, that are a `length` digit
+/// pattern repeated to fill the number. The patterns are consecutive, so the sum is an
+/// arithmetic series. Returns `None` if the sum overflows.
+fn sum_pattern(
+    low: u128,
+    high: u128,
+    digits: u32,
+    length: u32,
+    radix: u32,
+) -> Option<(u128, u128)> {
+    let Some((multiplier, first, last)) = pattern_bounds(low, high, digits, length, radix) else {
+        return Some((0, 0));
+    };
+
+    // halve whichever factor is even first, so only a sum too large for a u128 overflows
+    let count = last - first + 1;
+    let (pairs, pair_sum) = if count.is_multiple_of(2) {
+        (count / 2, first + last)
+    } else {
+        (count, (first + last) / 2)
+    };
+    let sum = multiplier.checked_mul(pair_sum)?.checked_mul(pairs)?;
+    Some((count, sum))
+}
+
+/// Sum of the numbers in the range made of a pattern repeated exactly twice, or `None` if it
+/// overflows
+fn sum_doubles(range: (u128, u128), radix: u32) -> Option<u128> {
+    by_digit_length(range, radix)
+        .filter(|(digits, _, _)| digits % 2 == 0)
+        .try_fold(0u128, |total, (digits, low, high)| {
+            total.checked_add(sum_pattern(low, high, digits, digits / 2, radix)?.1)
+        })
+}
+
+/// Count and sum the numbers in `low..=high`, all `digits` long, by the length of the shortest
+/// pattern that repeats to make them, as `(length, count, sum)`. Returns `None` if a sum
+/// overflows.
+fn repeats_by_period(
+    low: u128,
+    high: u128,
+    digits: u32,
+    radix: u32,
+) -> Option<Vec<(u32, u128, u128)>> {
+    let mut periods: Vec<(u32, u128, u128)> = Vec::new();
+
+    // A number made of a pattern of some length is also made of a pattern of any multiple of
+    // that length, e.g. 222222 is 2, 22 and 222 repeated. Going from the shortest length up,
+    // remove the numbers already counted with a shorter pattern that divides this one.
+    for length in (1..digits).filter(|length| digits.is_multiple_of(*length)) {
+        let (mut count, mut sum) = sum_pattern(low, high, digits, length, radix)?;
+        for &(shorter, shorter_count, shorter_sum) in &periods {
+            if length.is_multiple_of(shorter) {
+                count -= shorter_count;
+                sum -= shorter_sum;
+            }
+        }
+        periods.push((length, count, sum));
+    }
+
+    Some(periods)
+}
+
+/// Sum of the numbers in the range made of a pattern repeated at least twice, or `None` if it
+/// overflows
+fn sum_repeats(range: (u128, u128), radix: u32) -> Option<u128> {
+    let mut total = 0u128;
+    for (digits, low, high) in by_digit_length(range, radix) {
+        for (_, _, sum) in repeats_by_period(low, high, digits, radix)? {
+            total = total.checked_add(sum)?;
+        }
+    }
+    Some(total)
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+struct Tally {
+    count: u128,
+    sum: u128,
+}
+
+impl Tally {
+    fn add(&mut self, count: u128, sum: u128) -> Result<(), String> {
+        self.count = self
+            .count
+            .checked_add(count)
+            .ok_or("the count overflows a u128")?;
+        self.sum = self
+            .sum
+            .checked_add(sum)
+            .ok_or("the sum overflows a u128")?;
+        Ok(())
+    }
+}
+
+/// Count and sum the repeated IDs in the ranges for which `matches(length, repeats)` holds, given
+/// the length of the ID's shortest pattern and how many times it repeats, without visiting each ID
+fn tally(
+    ranges: &[(u128, u128)],
+    radix: u32,
+    matches: impl Fn(u32, u32) -> bool,
+) -> Result<Tally, String> {
+    let mut tally = Tally::default();
+
+    for &range in ranges {
+        for (digits, low, high) in by_digit_length(range, radix) {
+            let periods =
+                repeats_by_period(low, high, digits, radix).ok_or_else(|| overflow(range))?;
+            for (length, count, sum) in periods {
+                if matches(length, digits / length) {
+                    tally.add(count, sum)?;
+                }
             }
+        }
+    }
+
+    Ok(tally)
+}
+
+/// Every repeated ID in the range with its shortest pattern, in increasing order. The IDs are
+/// built from their patterns, so this takes time in proportion to how many there are.
+fn repeated_ids(range: (u128, u128), radix: u32) -> Vec<(u128, Repetition)> {
+    let mut ids = Vec::new();
+
+    for (digits, low, high) in by_digit_length(range, radix) {
+        for length in (1..digits).filter(|length| digits.is_multiple_of(*length)) {
+            let Some((multiplier, first, last)) = pattern_bounds(low, high, digits, length, radix)
+            else {
+                continue;
+            };
+            // skip the IDs that also repeat a shorter pattern, they're found with that length
+            ids.extend(
+                (first..=last)
+                    .map(|pattern| pattern * multiplier)
+                    .filter_map(|id| classify(id, radix).map(|repetition| (id, repetition)))
+                    .filter(|(_, repetition)| repetition.length == length),
+            );
+        }
+    }
+
+    ids.sort_unstable_by_key(|&(id, _)| id);
+    ids
+}
+
+/// Write `num` with the digits of the radix, padded with zeroes to at least `width` digits
+fn to_radix(mut num: u128, radix: u32, width: u32) -> String {
+    let mut digits = Vec::new();
+    while num > 0 || digits.len() < width.max(1) as usize {
+        let digit = (num % radix as u128) as u32;
+        digits.push(char::from_digit(digit, radix).unwrap());
+        num /= radix as u128;
+    }
+    digits.iter().rev().collect()
+}
+
+/// Explore the repeated IDs by the shape of their shortest pattern. With `--times K` or
+/// `--period P`, prints the count and sum of those repeated exactly K times or with a P digit
+/// pattern, otherwise a breakdown by pattern length and repeats. `--list` prints each ID instead.
+pub fn repeats_tool(args: &[String]) -> Result<(), String> {
+    let mut times = None;
+    let mut period = None;
+    let mut list = false;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .and_then(|value| value.parse::<u32>().ok())
+                .ok_or_else(|| format!("'{arg}' needs a number"))
+        };
+        match arg.as_str() {
+            "--times" => times = Some(value()?),
+            "--period" => period = Some(value()?),
+            "--list" => list = true,
+            _ => return Err(format!("unknown option '{arg}'")),
+        }
+    }
+
+    let input = crate::config::get().input(2)?;
+    let Settings { radix, lenient } = configured();
+    let merged = merged_ranges(&input, lenient)?;
+    if let Some(warning) = overlap_warning(&merged) {
+        eprintln!("Warning: {warning}");
+    }
+    let ranges = distinct(merged);
+    let matches = |length, repeats| {
+        times.is_none_or(|times| times == repeats) && period.is_none_or(|period| period == length)
+    };
 
-            // Truncate off the right part and compare it to the pattern and repeat until all have been compared
-            let pattern = num % 10u64.pow(length);
-            let mut remainder = (num - pattern) / 10u64.pow(length);
-            while remainder > 0 {
-                //println!("{num}/{pattern}: {remainder}");
-                let right = remainder % 10u64.pow(length);
-                if right != pattern {
-                    continue 'outer;
+    if list {
+        let ids = ranges.iter().flat_map(|&range| repeated_ids(range, radix));
+        for (id, repetition) in ids {
+            if matches(repetition.length, repetition.repeats) {
+                let Repetition {
+                    pattern,
+                    length,
+                    repeats,
+                } = repetition;
+                let pattern = to_radix(pattern, radix, length);
+                if radix == PUZZLE.radix {
+                    println!("{id} = {pattern} x{repeats}");
+                } else {
+                    let digits = to_radix(id, radix, 0);
+                    println!("{id} = {digits} = {pattern} x{repeats}");
                 }
-                remainder = (remainder - right) / 10u64.pow(length);
             }
-            return Some(num);
         }
-        None
-    })
+    } else if times.is_some() || period.is_some() {
+        let Tally { count, sum } = tally(&ranges, radix, matches)?;
+        println!("count: {count}");
+        println!("sum:   {sum}");
+    } else {
+        let mut breakdown = BTreeMap::<_, Tally>::new();
+        for &range in &ranges {
+            for (digits, low, high) in by_digit_length(range, radix) {
+                let periods =
+                    repeats_by_period(low, high, digits, radix).ok_or_else(|| overflow(range))?;
+                for (length, count, sum) in periods {
+                    breakdown
+                        .entry((length, digits / length))
+                        .or_default()
+                        .add(count, sum)?;
+                }
+            }
+        }
+
+        println!(
+            "{:>6} {:>7} {:>12} {:>40}",
+            "period", "repeats", "count", "sum"
+        );
+        for ((length, repeats), Tally { count, sum }) in breakdown {
+            if count > 0 {
+                println!("{length:>6} {repeats:>7} {count:>12} {sum:>40}");
+            }
+        }
+    }
+
+    Ok(())
 }
 
 #[cfg(test)]
@@ -86,28 +547,276 @@ mod tests {
 
     #[test]
     fn parsing() {
-        let mut ranges = super::parse_ranges(INPUT);
-        assert_eq!(ranges.next(), Some((11, 22)));
-        assert_eq!(ranges.next(), Some((95, 115)));
-        assert_eq!(ranges.next(), Some((998, 1012)));
-        assert_eq!(ranges.next(), Some((1188511880, 1188511890)));
-        assert_eq!(ranges.next(), Some((222220, 222224)));
-        assert_eq!(ranges.next(), Some((1698522, 1698528)));
-        assert_eq!(ranges.next(), Some((446443, 446449)));
-        assert_eq!(ranges.next(), Some((38593856, 38593862)));
-        assert_eq!(ranges.next(), Some((565653, 565659)));
-        assert_eq!(ranges.next(), Some((824824821, 824824827)));
-        assert_eq!(ranges.next(), Some((2121212118, 2121212124)));
-        assert_eq!(ranges.next(), None);
+        let ranges = super::parse_ranges(INPUT).unwrap();
+        assert_eq!(
+            ranges,
+            [
+                (11, 22),
+                (95, 115),
+                (998, 1012),
+                (1188511880, 1188511890),
+                (222220, 222224),
+                (1698522, 1698528),
+                (446443, 446449),
+                (38593856, 38593862),
+                (565653, 565659),
+                (824824821, 824824827),
+                (2121212118, 2121212124),
+            ]
+        );
+    }
+
+    #[test]
+    fn strict_parsing() {
+        let ranges = super::parse_ranges(" 1-2, 30-40\n").unwrap();
+        assert_eq!(ranges, [(1, 2), (30, 40)]);
+
+        let err = super::parse_ranges("11-22,12-,30-10,,x").unwrap_err();
+        assert_eq!(
+            err,
+            "invalid range '12-' at byte 6; reversed range '30-10' at byte 10; \
+             missing range at byte 16; invalid range 'x' at byte 17"
+        );
+        assert!(super::parse_ranges("1-2-3").is_err());
+        assert!(super::parse_ranges("").is_err());
+
+        // the lenient parser keeps whatever it can find
+        let ranges = super::parse_lenient("11-22,12-,30-10,,x").unwrap();
+        assert_eq!(ranges, [(11, 22), (30, 10)]);
+        let lenient = super::Settings {
+            lenient: true,
+            ..super::PUZZLE
+        };
+        assert_eq!(super::solution1("11-22,12-,x", lenient), Ok(33));
+        assert!(super::solution1("11-22,12-,x", super::PUZZLE).is_err());
     }
 
     #[test]
     fn part1() {
-        assert_eq!(super::solution1(INPUT), 1227775554);
+        assert_eq!(super::solution1(INPUT, super::PUZZLE), Ok(1227775554));
     }
 
     #[test]
     fn part2() {
-        assert_eq!(super::solution2(INPUT), 4174379265);
+        assert_eq!(super::solution2(INPUT, super::PUZZLE), Ok(4174379265));
+    }
+
+    #[test]
+    fn overlapping_ranges_counted_once() {
+        // 11-22 and 22-33 share 22, 95-115 swallows 99-100, and 1000-1009 is adjacent to 998-999
+        let overlapping = "11-22,22-33,95-115,99-100,998-999,1000-1009";
+        let ranges = super::distinct_ranges(overlapping, false).unwrap();
+        assert_eq!(ranges, [(11, 33), (95, 115), (998, 1009)]);
+        assert_eq!(
+            super::warnings(overlapping),
+            [
+                "overlapping day 2 ranges, counting their IDs once: 11-22 and 22-33, 95-115 and 99-100"
+            ]
+        );
+        assert!(super::warnings("11-22,23-33").is_empty());
+        assert_eq!(
+            super::solution1(overlapping, super::PUZZLE),
+            Ok(11 + 22 + 33 + 99)
+        );
+        assert_eq!(
+            super::part2_scan(overlapping),
+            super::part2("11-33,95-115,998-1009")
+        );
+    }
+
+    #[test]
+    fn scan_variants() {
+        assert_eq!(super::part1_scan(INPUT).unwrap(), "1227775554");
+        assert_eq!(super::part2_scan(INPUT).unwrap(), "4174379265");
+    }
+
+    #[test]
+    fn generated_match_scanned() {
+        // windows across every digit length up to 7, including boundaries like 999-1000
+        for start in (1..2_000_000)
+            .step_by(9_973)
+            .chain([1, 9, 99, 999_990, 222_200])
+        {
+            let range = (start, start + 25_000);
+            let doubles = super::find_doubles(range, 10).sum();
+            let repeats = super::find_repeats(range, 10).sum();
+            assert_eq!(super::sum_doubles(range, 10), Some(doubles), "{range:?}");
+            assert_eq!(super::sum_repeats(range, 10), Some(repeats), "{range:?}");
+        }
+    }
+
+    #[test]
+    fn repeated_with_several_lengths_counted_once() {
+        // 222222 is 2 six times, 22 three times and 222 twice
+        assert_eq!(super::sum_repeats((222222, 222222), 10), Some(222222));
+        assert_eq!(super::sum_doubles((222222, 222222), 10), Some(222222));
+        let elevens = (1..=9).map(|d| d * 11).sum();
+        assert_eq!(super::sum_repeats((1, 99), 10), Some(elevens));
+    }
+
+    #[test]
+    fn classification() {
+        let repetition = |pattern, length, repeats| {
+            Some(super::Repetition {
+                pattern,
+                length,
+                repeats,
+            })
+        };
+        assert_eq!(super::classify(123123123, 10), repetition(123, 3, 3));
+        assert_eq!(super::classify(222222, 10), repetition(2, 1, 6));
+        assert_eq!(super::classify(10101010, 10), repetition(10, 2, 4));
+        assert_eq!(super::classify(1001, 10), None);
+        assert_eq!(super::classify(7, 10), None);
+    }
+
+    #[test]
+    fn tallies() {
+        let ranges = super::parse_ranges(INPUT).unwrap();
+        let all = super::tally(&ranges, 10, |_, _| true).unwrap();
+        assert_eq!(all.sum, 4174379265);
+
+        // part 1 counts 222222 as 222 twice, but its shortest pattern is 2 six times
+        let twice = super::tally(&ranges, 10, |_, repeats| repeats == 2).unwrap();
+        assert_eq!(twice.sum, 1227775554 - 222222);
+        let thrice = super::tally(&ranges, 10, |_, repeats| repeats == 3).unwrap();
+        assert_eq!(
+            (thrice.count, thrice.sum),
+            (4, 111 + 999 + 565656 + 824824824)
+        );
+        let period_two = super::tally(&ranges, 10, |length, _| length == 2).unwrap();
+        assert_eq!(
+            (period_two.count, period_two.sum),
+            (3, 1010 + 565656 + 2121212121)
+        );
+
+        let ids: Vec<_> = ranges
+            .iter()
+            .flat_map(|&range| super::repeated_ids(range, 10))
+            .collect();
+        let scanned: Vec<_> = ranges
+            .iter()
+            .flat_map(|&range| super::find_repeats(range, 10))
+            .collect();
+        assert_eq!(ids.iter().map(|&(id, _)| id).collect::<Vec<_>>(), scanned);
+        assert_eq!(ids.len() as u128, all.count);
+    }
+
+    #[test]
+    fn huge_ranges() {
+        // every number under a trillion made of two identical halves, built from the halves
+        let expected: u128 = (1..=6)
+            .flat_map(|length| {
+                let multiplier = 10u128.pow(length) + 1;
+                (10u128.pow(length - 1)..10u128.pow(length)).map(move |half| half * multiplier)
+            })
+            .sum();
+        assert_eq!(super::sum_doubles((1, 999_999_999_999), 10), Some(expected));
+
+        // the smallest 20 digit double, just past the largest u64
+        let smallest = 10_000_000_001_000_000_000;
+        let range = (10_000_000_000_000_000_000, smallest);
+        assert_eq!(super::sum_doubles(range, 10), Some(smallest));
+        assert_eq!(
+            super::sum_repeats((smallest - 1, smallest), 10),
+            Some(smallest)
+        );
+
+        // the largest u128 has 39 digits, so the longest doubles are 38 digits
+        let half = 10u128.pow(19) - 1;
+        let largest = half * (10u128.pow(19) + 1);
+        assert_eq!(super::sum_doubles((largest, u128::MAX), 10), Some(largest));
+        assert_eq!(super::classify(largest, 10).map(|r| r.length), Some(1));
+        let ids = super::repeated_ids((largest - 1, largest + 1), 10);
+        assert_eq!(ids.iter().map(|&(id, _)| id).collect::<Vec<_>>(), [largest]);
+    }
+
+    #[test]
+    fn overflow_reported() {
+        // the 38 digit doubles alone sum to far more than a u128 holds
+        let input = format!("1-{}", u128::MAX);
+        assert!(
+            super::solution1(&input, super::PUZZLE)
+                .unwrap_err()
+                .contains("overflows")
+        );
+        assert!(
+            super::solution2(&input, super::PUZZLE)
+                .unwrap_err()
+                .contains("overflows")
+        );
+        assert_eq!(
+            super::part1(&input),
+            Err(format!(
+                "the sum of the repeated IDs in 1-{} overflows a u128",
+                u128::MAX
+            ))
+        );
+
+        // each sum fits, but not the total
+        let (twos, threes) = ("2".repeat(39), "3".repeat(39));
+        let input = format!("{twos}-{twos},{threes}-{threes}");
+        let single = super::sum_repeats((threes.parse().unwrap(), threes.parse().unwrap()), 10);
+        assert!(single.is_some_and(|sum| sum > u128::MAX / 2));
+        assert!(super::solution2(&input, super::PUZZLE).is_err());
+
+        let too_large = format!("1-{}0", u128::MAX);
+        assert!(super::parse_ranges(&too_large).is_err());
+    }
+
+    /// Whether the digits are some pattern repeated `repeats` times, or at least twice for `None`
+    fn repeats_in_string(digits: &str, repeats: Option<usize>) -> bool {
+        let digits = digits.as_bytes();
+        (1..=digits.len() / 2).any(|length| {
+            digits.len().is_multiple_of(length)
+                && repeats.is_none_or(|repeats| digits.len() / length == repeats)
+                && digits
+                    .chunks(length)
+                    .all(|chunk| chunk == &digits[..length])
+        })
+    }
+
+    #[test]
+    fn every_radix_matches_strings() {
+        for radix in 2..=36 {
+            for start in [1, 5_000, 123_456] {
+                let range = (start, start + 3_000);
+                let digits = |num| super::to_radix(num, radix, 0);
+                let doubles: Vec<u128> = (range.0..=range.1)
+                    .filter(|&num| repeats_in_string(&digits(num), Some(2)))
+                    .collect();
+                let repeats: Vec<u128> = (range.0..=range.1)
+                    .filter(|&num| repeats_in_string(&digits(num), None))
+                    .collect();
+
+                assert_eq!(
+                    super::find_doubles(range, radix).collect::<Vec<_>>(),
+                    doubles
+                );
+                assert_eq!(
+                    super::find_repeats(range, radix).collect::<Vec<_>>(),
+                    repeats
+                );
+                let sum = |ids: &[u128]| Some(ids.iter().sum());
+                assert_eq!(
+                    super::sum_doubles(range, radix),
+                    sum(&doubles),
+                    "{radix} {range:?}"
+                );
+                assert_eq!(
+                    super::sum_repeats(range, radix),
+                    sum(&repeats),
+                    "{radix} {range:?}"
+                );
+            }
+        }
+
+        // 0xabcabc is abc twice, 0b101010 is 10 three times
+        assert_eq!(
+            super::classify(0xabcabc, 16).map(|r| r.pattern),
+            Some(0xabc)
+        );
+        assert_eq!(super::classify(0b101010, 2).map(|r| r.repeats), Some(3));
+        assert_eq!(super::to_radix(35 * 36 + 10, 36, 0), "za");
     }
 }
@@ -1,24 +1,328 @@
-const INPUT: &str = include_str!("input/day3.txt");
+use std::fmt;
 
-pub fn part1() {
-    println!("{}", solution1(INPUT));
+use super::random::Lcg;
+use crate::bench;
+
+pub fn part1(input: &str) -> Result<String, String> {
+    solution1(input).map(|total| total.to_string())
+}
+
+pub fn part2(input: &str) -> Result<String, String> {
+    solution2(input).map(|total| total.to_string())
+}
+
+fn solution1(input: &str) -> Result<Total, String> {
+    solution(input, 2, max_n_digit_subsequence)
+}
+
+fn solution2(input: &str) -> Result<Total, String> {
+    solution(input, 12, max_n_digit_subsequence)
+}
+
+/// Sum the joltage chosen by `select` from each bank, which can be any number of digits long
+fn solution(input: &str, n: usize, select: fn(&str, usize) -> String) -> Result<Total, String> {
+    let mut total = Total::default();
+    for bank in parse_banks(input, n)? {
+        total.add(&select(bank, n));
+    }
+    Ok(total)
+}
+
+/// Check every line is made of digits and is long enough to choose `n` of them
+fn parse_banks(input: &str, n: usize) -> Result<Vec<&str>, String> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(number, line)| {
+            let number = number + 1;
+            // a character column, which is also the byte offset plus one since every character
+            // before the first non-digit is ASCII
+            if let Some((column, c)) = line.chars().enumerate().find(|(_, c)| !c.is_ascii_digit()) {
+                return Err(format!(
+                    "line {number}: '{c}' at column {} is not a digit",
+                    column + 1
+                ));
+            }
+            if line.len() < n {
+                return Err(format!(
+                    "line {number} has {} batteries, fewer than {n}",
+                    line.len()
+                ));
+            }
+            Ok(line)
+        })
+        .collect()
+}
+
+/// A sum of decimal numbers of any length
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+struct Total {
+    /// Least significant digit first
+    digits: Vec<u8>,
+}
+
+impl Total {
+    /// Add a number written in decimal digits
+    fn add(&mut self, number: &str) {
+        let mut carry = 0;
+        let mut added = number.bytes().rev().map(|digit| digit - b'0');
+        let mut place = 0;
+        loop {
+            let digit = added.next();
+            if digit.is_none() && carry == 0 && place >= self.digits.len() {
+                break;
+            }
+            if place == self.digits.len() {
+                self.digits.push(0);
+            }
+            let sum = self.digits[place] + digit.unwrap_or(0) + carry;
+            self.digits[place] = sum % 10;
+            carry = sum / 10;
+            place += 1;
+        }
+    }
+}
+
+impl fmt::Display for Total {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut digits = self.digits.iter().rev().skip_while(|&&digit| digit == 0);
+        let Some(first) = digits.next() else {
+            return write!(f, "0");
+        };
+        write!(f, "{first}")?;
+        digits.try_for_each(|digit| write!(f, "{digit}"))
+    }
+}
+
+fn max_n_digit_subsequence(line: &str, n: usize) -> String {
+    select_max(line, n)
+        .expect("banks are checked to have at least n batteries")
+        .joltage
+}
+
+/// The batteries turned on in a bank and the joltage they produce
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Selection {
+    /// Positions of the chosen digits in the line, in increasing order
+    pub indices: Vec<usize>,
+    /// The chosen digits, which may be too many for any integer type
+    pub joltage: String,
 }
 
-pub fn part2() {
-    println!("{}", solution2(INPUT));
+/// Choose the `n` digits of the line that make the largest number, keeping their order. Scans the
+/// line once, keeping the digits chosen so far on a stack. A digit replaces the smaller ones
+/// before it while there are still enough digits left to make up the number. `None` if the line
+/// has fewer than `n` digits.
+pub fn select_max(line: &str, n: usize) -> Option<Selection> {
+    let bytes = line.as_bytes();
+    let mut stack: Vec<usize> = Vec::with_capacity(n);
+    let mut skips = bytes.len().checked_sub(n)?;
+
+    for (index, &digit) in bytes.iter().enumerate() {
+        while skips > 0 && stack.last().is_some_and(|&last| bytes[last] < digit) {
+            stack.pop();
+            skips -= 1;
+        }
+        if stack.len() < n {
+            stack.push(index);
+        } else {
+            // full, so this digit is skipped
+            skips -= 1;
+        }
+    }
+
+    let joltage = stack.iter().map(|&index| bytes[index] as char).collect();
+    Some(Selection {
+        indices: stack,
+        joltage,
+    })
 }
 
-fn solution1(input: &str) -> u64 {
-    input.lines().map(|l| max_n_digit_subsequence(l, 2)).sum()
+/// Which number the chosen digits should make
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Goal {
+    #[default]
+    Largest,
+    Smallest,
 }
 
-fn solution2(input: &str) -> u64 {
-    input.lines().map(|l| max_n_digit_subsequence(l, 12)).sum()
+/// Limits on which batteries can be chosen together
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Constraints {
+    pub goal: Goal,
+    /// The first chosen digit can't be a zero
+    pub no_leading_zero: bool,
+    /// Batteries left unchosen between each pair of chosen ones, at least
+    pub min_gap: usize,
+    /// Distance from the first chosen battery to the last, at most
+    pub max_span: Option<usize>,
 }
 
-fn max_n_digit_subsequence(line: &str, n: usize) -> u64 {
+/// Choose the `n` digits of the line that make the largest or smallest number allowed by the
+/// constraints, or `None` if no choice satisfies them. Without constraints this is
+/// [`select_max`], otherwise each digit is picked in turn as the best that still leaves room for
+/// the rest, taking O(n·len), or O(n·len²) with a span limit.
+pub fn select(line: &str, n: usize, constraints: &Constraints) -> Option<Selection> {
+    if *constraints == Constraints::default() {
+        return select_max(line, n);
+    }
+
     let bytes = line.as_bytes();
-    let mut result = Vec::with_capacity(n);
+    let step = constraints.min_gap + 1;
+    let last = bytes.len().checked_sub(1)?;
+    let allowed_first = |index: usize| !(constraints.no_leading_zero && bytes[index] == b'0');
+
+    let indices = match constraints.max_span {
+        None => pick(bytes, n, 0, last, step, constraints.goal, &allowed_first),
+        // picking an earlier first battery moves in the furthest one that can be reached, so
+        // the best choice following each possible first battery is compared
+        Some(span) => (0..bytes.len())
+            .filter(|&first| allowed_first(first))
+            .filter_map(|first| {
+                if n == 0 {
+                    return Some(Vec::new());
+                }
+                let limit = last.min(first + span);
+                let mut indices = vec![first];
+                indices.extend(pick(
+                    bytes,
+                    n - 1,
+                    first + step,
+                    limit,
+                    step,
+                    constraints.goal,
+                    &|_| true,
+                )?);
+                Some(indices)
+            })
+            .reduce(|best, indices| {
+                let digits =
+                    |indices: &[usize]| indices.iter().map(|&i| bytes[i]).collect::<Vec<_>>();
+                let better = match constraints.goal {
+                    Goal::Largest => digits(&indices) > digits(&best),
+                    Goal::Smallest => digits(&indices) < digits(&best),
+                };
+                if better { indices } else { best }
+            }),
+    }?;
+
+    let joltage = indices.iter().map(|&index| bytes[index] as char).collect();
+    Some(Selection { indices, joltage })
+}
+
+/// Greedily pick `count` indices from `from..=limit`, at least `step` apart, making the best
+/// number for the goal. The first pick must also satisfy `allowed_first`.
+fn pick(
+    bytes: &[u8],
+    count: usize,
+    mut from: usize,
+    limit: usize,
+    step: usize,
+    goal: Goal,
+    allowed_first: &dyn Fn(usize) -> bool,
+) -> Option<Vec<usize>> {
+    let mut indices = Vec::with_capacity(count);
+    for picked in 0..count {
+        // leave enough room after this pick for the rest
+        let latest = limit.checked_sub((count - picked - 1) * step)?;
+        if from > latest {
+            return None;
+        }
+        let candidates = (from..=latest).filter(|&index| picked > 0 || allowed_first(index));
+        // the earliest of the best digits leaves the most room for the rest
+        let index = match goal {
+            Goal::Largest => candidates.rev().max_by_key(|&index| bytes[index]),
+            Goal::Smallest => candidates.min_by_key(|&index| bytes[index]),
+        }?;
+        indices.push(index);
+        from = index + step;
+    }
+    Some(indices)
+}
+
+/// Print each bank with the batteries chosen for it highlighted, followed by its joltage.
+/// `--digits N` chooses N batteries instead of the 12 of part 2, and `--plain` marks them with
+/// brackets rather than colour. The choice can be changed with `--smallest`, `--no-leading-zero`,
+/// `--min-gap G` and `--max-span S`.
+pub fn show_tool(args: &[String]) -> Result<(), String> {
+    let mut digits = 12;
+    let mut plain = false;
+    let mut constraints = Constraints::default();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .and_then(|value| value.parse::<usize>().ok())
+                .ok_or_else(|| format!("'{arg}' needs a number"))
+        };
+        match arg.as_str() {
+            "--digits" => {
+                digits = value()?;
+                if digits == 0 {
+                    return Err(format!("'{arg}' needs a positive number"));
+                }
+            }
+            "--smallest" => constraints.goal = Goal::Smallest,
+            "--no-leading-zero" => constraints.no_leading_zero = true,
+            "--min-gap" => constraints.min_gap = value()?,
+            "--max-span" => constraints.max_span = Some(value()?),
+            "--plain" => plain = true,
+            _ => return Err(format!("unknown option '{arg}'")),
+        }
+    }
+
+    let input = crate::config::get().input(3)?;
+    let mut total = Total::default();
+    for line in parse_banks(&input, digits)? {
+        let Some(selection) = select(line, digits, &constraints) else {
+            println!("{line}  no choice fits the constraints");
+            continue;
+        };
+        println!(
+            "{}  {}",
+            highlight(line, &selection.indices, plain),
+            selection.joltage
+        );
+        total.add(&selection.joltage);
+    }
+    println!("total: {total}");
+
+    Ok(())
+}
+
+/// The line with the digits at `indices` in bold green, or in brackets when `plain`
+fn highlight(line: &str, indices: &[usize], plain: bool) -> String {
+    let (start, end) = if plain {
+        ("[", "]")
+    } else {
+        ("\x1b[1;32m", "\x1b[0m")
+    };
+    let mut chosen = indices.iter().peekable();
+    let mut shown = String::new();
+    for (index, digit) in line.char_indices() {
+        if chosen.next_if_eq(&&index).is_some() {
+            shown.push_str(start);
+            shown.push(digit);
+            shown.push_str(end);
+        } else {
+            shown.push(digit);
+        }
+    }
+    shown
+}
+
+/// Search the window of digits that could come next for the largest, once per output digit
+pub fn part1_window(input: &str) -> Result<String, String> {
+    solution(input, 2, window_subsequence).map(|total| total.to_string())
+}
+
+pub fn part2_window(input: &str) -> Result<String, String> {
+    solution(input, 12, window_subsequence).map(|total| total.to_string())
+}
+
+fn window_subsequence(line: &str, n: usize) -> String {
+    let bytes = line.as_bytes();
+    let mut result = String::with_capacity(n);
 
     let mut start = 0;
     let remaining = n;
@@ -37,13 +341,66 @@ fn max_n_digit_subsequence(line: &str, n: usize) -> u64 {
             .max_by_key(|&(_, d)| d)
             .unwrap();
 
-        result.push(digit);
+        result.push(digit as char);
         start += idx + 1;
     }
 
-    // SAFETY: result derived from valid utf8 string
-    let s = unsafe { std::str::from_utf8_unchecked(&result) };
-    s.parse::<u64>().unwrap()
+    result
+}
+
+/// Time the stack and window implementations against each other on generated banks. Options are
+/// `--banks N`, `--length L` and `--digits D`, for N banks of L batteries choosing D of each.
+pub fn compare_tool(args: &[String]) -> Result<(), String> {
+    let (mut banks, mut length, mut digits) = (1000, 10_000, 100);
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .and_then(|value| value.parse::<usize>().ok())
+                .filter(|&value| value > 0)
+                .ok_or_else(|| format!("'{arg}' needs a positive number"))
+        };
+        match arg.as_str() {
+            "--banks" => banks = value()?,
+            "--length" => length = value()?,
+            "--digits" => digits = value()?,
+            _ => return Err(format!("unknown option '{arg}'")),
+        }
+    }
+    if digits > length {
+        return Err("'--digits' can't be more than '--length'".to_string());
+    }
+
+    let input = generate_banks(banks, length);
+    let iterations = crate::config::get().iterations.value;
+    let time = |select| bench::time(iterations, || solution(&input, digits, select));
+
+    let (stack_sum, stack) = time(max_n_digit_subsequence);
+    let (window_sum, window) = time(window_subsequence);
+    let (stack_sum, window_sum) = (stack_sum?, window_sum?);
+    if stack_sum != window_sum {
+        return Err(format!(
+            "answers differ, {stack_sum} from the stack and {window_sum} from the window"
+        ));
+    }
+
+    println!("{banks} banks of {length}, choosing {digits}, sum {stack_sum}");
+    bench::print_comparison(("stack", stack), ("window", window));
+
+    Ok(())
+}
+
+/// Banks of random digits from 1 to 9, like the puzzle's
+fn generate_banks(banks: usize, length: usize) -> String {
+    let mut lcg = Lcg::new(12345);
+    let mut input = String::with_capacity(banks * (length + 1));
+    for _ in 0..banks {
+        for _ in 0..length {
+            input.push((b'1' + lcg.below(9) as u8) as char);
+        }
+        input.push('\n');
+    }
+    input
 }
 
 #[cfg(test)]
@@ -52,19 +409,193 @@ mod tests {
 
     #[test]
     fn part1() {
-        assert_eq!(super::solution1(INPUT), 357);
+        assert_eq!(super::part1(INPUT).unwrap(), "357");
     }
 
     #[test]
     fn part2() {
-        assert_eq!(super::solution2(INPUT), 3121910778619);
+        assert_eq!(super::part2(INPUT).unwrap(), "3121910778619");
+    }
+
+    #[test]
+    fn window_variant() {
+        assert_eq!(super::part1_window(INPUT).unwrap(), "357");
+        assert_eq!(super::part2_window(INPUT).unwrap(), "3121910778619");
+    }
+
+    #[test]
+    fn stack_matches_window() {
+        let banks = super::generate_banks(50, 40);
+        for line in banks.lines().chain(["11111", "98765", "12345", "90909"]) {
+            for n in 1..=line.len() {
+                assert_eq!(
+                    super::max_n_digit_subsequence(line, n),
+                    super::window_subsequence(line, n),
+                    "{line} {n}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn selected_indices() {
+        let selection = super::select_max("818181911112111", 12).unwrap();
+        assert_eq!(selection.joltage, "888911112111");
+        assert_eq!(selection.indices, [0, 2, 4, 6, 7, 8, 9, 10, 11, 12, 13, 14]);
+        assert_eq!(
+            super::select_max("811111111111119", 2).unwrap().indices,
+            [0, 14]
+        );
+        assert_eq!(super::select_max("81", 3), None);
+
+        assert_eq!(super::highlight("8119", &[0, 3], true), "[8]11[9]");
+        assert_eq!(super::highlight("19", &[1], false), "1\x1b[1;32m9\x1b[0m");
     }
 
     #[test]
     fn pt2_examples() {
-        assert_eq!(super::solution2("987654321111111"), 987654321111);
-        assert_eq!(super::solution2("811111111111119"), 811111111119);
-        assert_eq!(super::solution2("234234234234278"), 434234234278);
-        assert_eq!(super::solution2("818181911112111"), 888911112111);
+        assert_eq!(super::part2("987654321111111").unwrap(), "987654321111");
+        assert_eq!(super::part2("811111111111119").unwrap(), "811111111119");
+        assert_eq!(super::part2("234234234234278").unwrap(), "434234234278");
+        assert_eq!(super::part2("818181911112111").unwrap(), "888911112111");
+    }
+
+    #[test]
+    fn long_joltages() {
+        // 40 nines is past the largest u128
+        let bank = "9".repeat(45);
+        let input = format!("{bank}\n{bank}\n");
+        let total = super::solution(&input, 40, super::max_n_digit_subsequence).unwrap();
+        let expected = format!("1{}8", "9".repeat(39));
+        assert_eq!(total.to_string(), expected);
+
+        let mut total = super::Total::default();
+        assert_eq!(total.to_string(), "0");
+        total.add("0099");
+        total.add("1");
+        assert_eq!(total.to_string(), "100");
+    }
+
+    /// Every way of choosing `n` of the line's digits that fits the constraints, keeping the best
+    fn brute_force(line: &str, n: usize, constraints: &super::Constraints) -> Option<String> {
+        let bytes = line.as_bytes();
+        let mut best: Option<String> = None;
+        for mask in 0u32..1 << bytes.len() {
+            if mask.count_ones() as usize != n {
+                continue;
+            }
+            let indices: Vec<usize> = (0..bytes.len()).filter(|i| mask & 1 << i != 0).collect();
+            let fits = indices.first().is_none_or(|&first| {
+                !(constraints.no_leading_zero && bytes[first] == b'0')
+                    && indices
+                        .windows(2)
+                        .all(|w| w[1] - w[0] > constraints.min_gap)
+                    && constraints
+                        .max_span
+                        .is_none_or(|span| indices[indices.len() - 1] - first <= span)
+            });
+            if !fits {
+                continue;
+            }
+            let digits: String = indices.iter().map(|&i| bytes[i] as char).collect();
+            let better = best.as_ref().is_none_or(|best| match constraints.goal {
+                super::Goal::Largest => digits > *best,
+                super::Goal::Smallest => digits < *best,
+            });
+            if better {
+                best = Some(digits);
+            }
+        }
+        best
+    }
+
+    #[test]
+    fn engine_matches_brute_force() {
+        let mut lcg = super::Lcg::new(7);
+        let lines: Vec<String> = (0..40)
+            .map(|_| {
+                (0..9)
+                    // few distinct digits, so ties and zeros are common
+                    .map(|_| (b'0' + lcg.below(4) as u8) as char)
+                    .collect()
+            })
+            .collect();
+
+        for line in &lines {
+            for n in 1..=4 {
+                for goal in [super::Goal::Largest, super::Goal::Smallest] {
+                    for no_leading_zero in [false, true] {
+                        for min_gap in 0..=2 {
+                            for max_span in [None, Some(3), Some(5)] {
+                                let constraints = super::Constraints {
+                                    goal,
+                                    no_leading_zero,
+                                    min_gap,
+                                    max_span,
+                                };
+                                let selection = super::select(line, n, &constraints);
+                                assert_eq!(
+                                    selection.map(|selection| selection.joltage),
+                                    brute_force(line, n, &constraints),
+                                    "{line} {n} {constraints:?}"
+                                );
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn constrained_selections() {
+        let smallest = super::Constraints {
+            goal: super::Goal::Smallest,
+            ..Default::default()
+        };
+        assert_eq!(super::select("3102", 2, &smallest).unwrap().joltage, "02");
+        let no_zero = super::Constraints {
+            no_leading_zero: true,
+            ..smallest
+        };
+        let selection = super::select("3102", 2, &no_zero).unwrap();
+        assert_eq!(
+            (selection.joltage.as_str(), selection.indices),
+            ("10", vec![1, 2])
+        );
+
+        let gapped = super::Constraints {
+            min_gap: 2,
+            ..Default::default()
+        };
+        assert_eq!(super::select("99181", 2, &gapped).unwrap().joltage, "98");
+        assert_eq!(super::select("991", 2, &gapped), None);
+
+        let spanned = super::Constraints {
+            max_span: Some(1),
+            ..Default::default()
+        };
+        assert_eq!(super::select("91181", 2, &spanned).unwrap().joltage, "91");
+        assert_eq!(super::select("19181", 2, &spanned).unwrap().joltage, "91");
+    }
+
+    #[test]
+    fn invalid_banks() {
+        assert_eq!(
+            super::solution1("1234\n12a4\n").unwrap_err(),
+            "line 2: 'a' at column 3 is not a digit"
+        );
+        assert_eq!(
+            super::solution2("987654321111111\n123\n").unwrap_err(),
+            "line 2 has 3 batteries, fewer than 12"
+        );
+        assert_eq!(
+            super::part1("12\n-1\n"),
+            Err("line 2: '-' at column 1 is not a digit".to_string())
+        );
+        assert_eq!(
+            super::part1_window("12\n1é1\n").unwrap_err(),
+            "line 2: 'é' at column 2 is not a digit"
+        );
     }
 }
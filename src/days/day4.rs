@@ -1,44 +1,134 @@
 use std::collections::HashSet;
-
-const INPUT: &str = include_str!("input/day4.txt");
-
-pub fn part1() {
-    println!("{}", solution1(INPUT));
+use std::fmt;
+use std::fs;
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::thread;
+use std::time::Duration;
+
+use super::grid::{FromChar, Grid};
+use super::random::Lcg;
+use crate::bench;
+use crate::config::Neighbourhood;
+
+/// When a paper roll can be removed
+#[derive(Debug, Clone, Copy)]
+struct Rules {
+    /// A roll can be removed when fewer than this many of its neighbours are rolls
+    threshold: usize,
+    neighbourhood: Neighbourhood,
+    radius: usize,
+    /// Whether the grid wraps around at its edges, rather than the cells past them being empty
+    wrap: bool,
 }
 
-pub fn part2() {
-    println!("{}", solution2(INPUT));
+/// The puzzle removes rolls with fewer than four rolls in the eight cells around them
+#[cfg(test)]
+const PUZZLE: Rules = Rules {
+    threshold: 4,
+    neighbourhood: Neighbourhood::Moore,
+    radius: 1,
+    wrap: false,
+};
+
+impl Rules {
+    /// Offsets to every cell in the neighbourhood, other than the cell itself
+    fn offsets(&self) -> Vec<(isize, isize)> {
+        let radius = self.radius as isize;
+        let mut offsets = Vec::new();
+        for dy in -radius..=radius {
+            for dx in -radius..=radius {
+                let inside = match self.neighbourhood {
+                    Neighbourhood::VonNeumann => dx.abs() + dy.abs() <= radius,
+                    Neighbourhood::Moore => true,
+                };
+                if inside && (dx, dy) != (0, 0) {
+                    offsets.push((dx, dy));
+                }
+            }
+        }
+        offsets
+    }
 }
 
-#[derive(Debug)]
-struct Grid {
-    width: usize,
-    height: usize,
-    cells: Vec<Cell>,
+/// Finds the neighbours of a cell under some rules
+struct Neighbours {
+    offsets: Vec<(isize, isize)>,
+    wrap: bool,
 }
 
-impl Grid {
-    fn get(&self, x: usize, y: usize) -> Cell {
-        self.cells[y * self.width + x]
+impl Neighbours {
+    /// The neighbours under the rules on a grid. When wrapping on a grid smaller than the
+    /// neighbourhood, offsets that reach the same cell are kept once and those that come back
+    /// round to the cell itself are dropped.
+    fn new<T>(rules: &Rules, grid: &Grid<T>) -> Self {
+        let mut offsets = rules.offsets();
+        if rules.wrap {
+            let (width, height) = (grid.width() as isize, grid.height() as isize);
+            let mut seen = HashSet::new();
+            offsets = offsets
+                .into_iter()
+                .map(|(dx, dy)| (dx.rem_euclid(width), dy.rem_euclid(height)))
+                .filter(|&offset| offset != (0, 0) && seen.insert(offset))
+                .collect();
+        }
+        Neighbours {
+            offsets,
+            wrap: rules.wrap,
+        }
     }
 
-    fn get_mut(&mut self, x: usize, y: usize) -> &mut Cell {
-        &mut self.cells[y * self.width + x]
+    /// The neighbours of `position`
+    fn of<'a, T>(
+        &'a self,
+        grid: &'a Grid<T>,
+        position: (usize, usize),
+    ) -> impl Iterator<Item = (usize, usize)> + 'a {
+        self.offsets.iter().filter_map(move |&offset| {
+            if self.wrap {
+                Some(grid.offset_wrapping(position, offset))
+            } else {
+                grid.offset(position, offset)
+            }
+        })
     }
 
-    fn neighbors(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
-        [
-            (y > 0 && x > 0).then(|| (x - 1, y - 1)),
-            (y > 0).then(|| (x, y - 1)),
-            (y > 0 && x < self.width - 1).then(|| (x + 1, y - 1)),
-            (x > 0).then(|| (x - 1, y)),
-            (x < self.width - 1).then(|| (x + 1, y)),
-            (y < self.height - 1 && x > 0).then(|| (x - 1, y + 1)),
-            (y < self.height - 1).then(|| (x, y + 1)),
-            (y < self.height - 1 && x < self.width - 1).then(|| (x + 1, y + 1)),
-        ]
-        .into_iter()
-        .flatten()
+    /// How many neighbours of `position` are paper rolls
+    fn paper(&self, grid: &Grid<Cell>, position: (usize, usize)) -> usize {
+        self.of(grid, position)
+            .filter(|&neighbour| grid[neighbour] == Cell::Paper)
+            .count()
+    }
+}
+
+pub fn part1(input: &str) -> Result<String, String> {
+    Ok(solution1(input, configured()).to_string())
+}
+
+pub fn part2(input: &str) -> Result<String, String> {
+    Ok(solution2(input, configured()).to_string())
+}
+
+/// Count with rows of packed bits rather than one cell at a time
+pub fn part1_bitset(input: &str) -> Result<String, String> {
+    let grid = parse_grid(input);
+    let counts = erode_bits(&grid, &configured(), 1)?;
+    Ok(counts.iter().sum::<usize>().to_string())
+}
+
+pub fn part2_bitset(input: &str) -> Result<String, String> {
+    let grid = parse_grid(input);
+    let counts = erode_bits(&grid, &configured(), usize::MAX)?;
+    Ok(counts.iter().sum::<usize>().to_string())
+}
+
+fn configured() -> Rules {
+    let config = crate::config::get();
+    Rules {
+        threshold: config.day4_threshold.value,
+        neighbourhood: config.day4_neighbourhood.value,
+        radius: config.day4_radius.value,
+        wrap: config.day4_wrap.value,
     }
 }
 
@@ -48,119 +138,536 @@ enum Cell {
     Paper,
 }
 
-fn parse_grid(input: &str) -> Grid {
-    let mut width = 0;
-    let mut height = 0;
-    let mut cells = Vec::with_capacity(input.len());
-
-    for line in input.lines() {
-        assert!(!line.is_empty(), "empty line in input");
-
-        height += 1;
-
-        if width == 0 {
-            width = line.len()
-        } else {
-            assert_eq!(width, line.len(), "mismatched line widths in grid");
+impl FromChar for Cell {
+    fn from_char(c: char) -> Option<Self> {
+        match c {
+            '.' => Some(Cell::Empty),
+            '@' => Some(Cell::Paper),
+            _ => None,
         }
-
-        cells.extend(line.as_bytes().iter().map(|c| match c {
-            b'.' => Cell::Empty,
-            b'@' => Cell::Paper,
-            _ => panic!("invalid character input '{}'", c),
-        }));
     }
-    Grid {
-        width,
-        height,
-        cells,
+}
+
+impl fmt::Display for Cell {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Cell::Empty => write!(f, "."),
+            Cell::Paper => write!(f, "@"),
+        }
     }
 }
 
-fn solution1(input: &str) -> usize {
+fn parse_grid(input: &str) -> Grid<Cell> {
+    Grid::parse(input).unwrap_or_else(|err| panic!("invalid puzzle input: {err}"))
+}
+
+fn solution1(input: &str, rules: Rules) -> usize {
     let grid = parse_grid(input);
-    let paper = get_paper_to_move(&grid);
+    let paper = get_paper_to_move(&grid, &rules);
     paper.len()
 }
 
-fn solution2(input: &str) -> usize {
-    let mut grid = parse_grid(input);
-    let mut neighbor_counts = vec![0u8; grid.cells.len()];
-    let mut removed_paper = 0;
+fn solution2(input: &str, rules: Rules) -> usize {
+    let grid = parse_grid(input);
+    erode(&grid, &rules).counts.iter().sum()
+}
+
+/// The order the paper rolls were removed in
+struct Erosion {
+    /// The wave each roll was removed in, numbered from 1, or `None` if it never was
+    waves: Grid<Option<usize>>,
+    /// Rolls removed in each wave
+    counts: Vec<usize>,
+}
+
+/// Remove paper rolls in waves until none can be, each wave taking every roll with fewer than
+/// the threshold of neighbours at its start
+fn erode(grid: &Grid<Cell>, rules: &Rules) -> Erosion {
+    let neighbours = Neighbours::new(rules, grid);
+    let mut grid = grid.clone();
+    let mut neighbor_counts = vec![0; grid.cells().len()];
+    let mut waves = grid.map(|_, _| None);
+    let mut counts = Vec::new();
     let mut to_check = Vec::new();
 
     // cache the number of adjacent cells that are paper for each cell
-    // and if the count is less than four, add it to the cells to check
-    for y in 0..grid.height {
-        for x in 0..grid.width {
-            if grid.get(x, y) == Cell::Paper {
-                let count = grid
-                    .neighbors(x, y)
-                    .filter(|(nx, ny)| grid.get(*nx, *ny) == Cell::Paper)
-                    .count();
-                neighbor_counts[y * grid.width + x] = count as u8;
-                if count < 4 {
-                    to_check.push((x, y))
-                };
-            }
+    // and if the count is below the threshold, add it to the cells to check
+    for ((x, y), &cell) in grid.iter() {
+        if cell == Cell::Paper {
+            let count = neighbours.paper(&grid, (x, y));
+            neighbor_counts[grid.index_of(x, y)] = count;
+            if count < rules.threshold {
+                to_check.push((x, y))
+            };
         }
     }
 
     while !to_check.is_empty() {
+        // decide every removal before making any, so the wave a roll is removed in doesn't
+        // depend on the order the cells are checked
+        let removed: Vec<_> = to_check
+            .iter()
+            .copied()
+            .filter(|&(x, y)| {
+                grid[(x, y)] == Cell::Paper
+                    && neighbor_counts[grid.index_of(x, y)] < rules.threshold
+            })
+            .collect();
+        if removed.is_empty() {
+            break;
+        }
+        counts.push(removed.len());
+        for &position in &removed {
+            grid[position] = Cell::Empty;
+            waves[position] = Some(counts.len());
+        }
+
+        // fix neighbor count for adjacent cells and add neighbours in the next cells to check
         let mut next_to_check = HashSet::new();
+        for &position in &removed {
+            for (nx, ny) in neighbours.of(&grid, position) {
+                let n_idx = grid.index_of(nx, ny);
+                if grid[(nx, ny)] == Cell::Paper {
+                    neighbor_counts[n_idx] -= 1;
+                    next_to_check.insert((nx, ny));
+                }
+            }
+        }
+
+        to_check.clear();
+        to_check.extend(next_to_check.iter());
+    }
 
-        for &(x, y) in &to_check {
-            let idx = y * grid.width + x;
+    Erosion { waves, counts }
+}
 
-            if grid.get(x, y) == Cell::Empty {
-                continue;
-            }
+/// The mark for a roll removed in a wave: 1 to 9, then a to z, then + for any later wave
+fn wave_mark(wave: usize) -> char {
+    char::from_digit(wave as u32, 36)
+        .filter(|_| wave > 0)
+        .unwrap_or('+')
+}
 
-            if neighbor_counts[idx] < 4 {
-                *grid.get_mut(x, y) = Cell::Empty;
-                removed_paper += 1;
+/// Print how many paper rolls each wave removes, then the grid with each roll marked by the wave
+/// that removed it. Rolls that are never removed are left as `@`.
+pub fn waves_tool(args: &[String]) -> Result<(), String> {
+    if let Some(arg) = args.first() {
+        return Err(format!("unknown option '{arg}'"));
+    }
 
-                // fix neighbor count for adjacent cells and add neighbours in the next cells to check
-                for (nx, ny) in grid.neighbors(x, y) {
-                    let n_idx = ny * grid.width + nx;
-                    if grid.get(nx, ny) == Cell::Paper {
-                        neighbor_counts[n_idx] -= 1;
-                        next_to_check.insert((nx, ny));
-                    }
+    let input = crate::config::get().input(4)?;
+    let grid = Grid::<Cell>::parse(&input)?;
+    let erosion = erode(&grid, &configured());
+
+    println!("{:>4} {:>8}", "wave", "removed");
+    for (wave, count) in erosion.counts.iter().enumerate() {
+        println!("{:>4} {count:>8}", wave + 1);
+    }
+    println!();
+    print!("{}", annotate(&grid, &erosion.waves));
+
+    let kept = grid
+        .iter()
+        .filter(|&(position, &cell)| cell == Cell::Paper && erosion.waves[position].is_none())
+        .count();
+    println!();
+    println!(
+        "{} rolls removed in {} waves, {kept} never removable",
+        erosion.counts.iter().sum::<usize>(),
+        erosion.counts.len()
+    );
+
+    Ok(())
+}
+
+/// The grid with each removed roll replaced by the mark for its wave
+fn annotate(grid: &Grid<Cell>, waves: &Grid<Option<usize>>) -> Grid<char> {
+    grid.map(|position, &cell| match (cell, waves[position]) {
+        (Cell::Paper, Some(wave)) => wave_mark(wave),
+        (Cell::Paper, None) => '@',
+        (Cell::Empty, _) => '.',
+    })
+}
+
+fn get_paper_to_move(grid: &Grid<Cell>, rules: &Rules) -> HashSet<(usize, usize)> {
+    let neighbours = Neighbours::new(rules, grid);
+    let mut paper_to_move = HashSet::new();
+
+    for ((x, y), &current) in grid.iter() {
+        if current == Cell::Empty {
+            continue;
+        }
+
+        if neighbours.paper(grid, (x, y)) < rules.threshold {
+            paper_to_move.insert((x, y));
+        }
+    }
+
+    paper_to_move
+}
+
+/// How a cell looks in one frame of an animation of the removal
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Stage {
+    Empty,
+    Paper,
+    /// Removed in the frame's wave
+    Removing,
+    /// Removed in an earlier wave
+    Removed,
+}
+
+impl Stage {
+    const fn ansi(self) -> &'static str {
+        match self {
+            Stage::Empty => "\x1b[90m.\x1b[0m",
+            Stage::Paper => "\x1b[32m@\x1b[0m",
+            Stage::Removing => "\x1b[1;31m@\x1b[0m",
+            Stage::Removed => "\x1b[33mx\x1b[0m",
+        }
+    }
+
+    const fn rgb(self) -> [u8; 3] {
+        match self {
+            Stage::Empty => [24, 24, 24],
+            Stage::Paper => [236, 232, 214],
+            Stage::Removing => [214, 58, 42],
+            Stage::Removed => [92, 70, 48],
+        }
+    }
+}
+
+/// The grid while wave `wave` is removed, where wave 0 is before any and the wave after the last
+/// is once they all have been
+fn frame(grid: &Grid<Cell>, waves: &Grid<Option<usize>>, wave: usize) -> Grid<Stage> {
+    grid.map(|position, &cell| match (cell, waves[position]) {
+        (Cell::Empty, _) => Stage::Empty,
+        (Cell::Paper, Some(removed)) if removed < wave => Stage::Removed,
+        (Cell::Paper, Some(removed)) if removed == wave => Stage::Removing,
+        (Cell::Paper, _) => Stage::Paper,
+    })
+}
+
+/// A binary PPM image of a frame, with each cell `scale` pixels across
+fn ppm(frame: &Grid<Stage>, scale: usize) -> Vec<u8> {
+    let (width, height) = (frame.width() * scale, frame.height() * scale);
+    let mut image = format!("P6\n{width} {height}\n255\n").into_bytes();
+    for row in frame.rows() {
+        let line: Vec<u8> = row
+            .iter()
+            .flat_map(|stage| stage.rgb().repeat(scale))
+            .collect();
+        for _ in 0..scale {
+            image.extend_from_slice(&line);
+        }
+    }
+    image
+}
+
+/// A binary PBM image of a frame, black wherever a roll is still in place, so the rolls being
+/// removed are white along with the ones already gone
+fn pbm(frame: &Grid<Stage>, scale: usize) -> Vec<u8> {
+    let (width, height) = (frame.width() * scale, frame.height() * scale);
+    let mut image = format!("P4\n{width} {height}\n").into_bytes();
+    for row in frame.rows() {
+        // each line of pixels is padded to a whole number of bytes
+        let mut line = vec![0u8; width.div_ceil(8)];
+        for (x, &stage) in row.iter().enumerate() {
+            if stage == Stage::Paper {
+                for pixel in x * scale..(x + 1) * scale {
+                    line[pixel / 8] |= 0x80 >> (pixel % 8);
                 }
             }
         }
+        for _ in 0..scale {
+            image.extend_from_slice(&line);
+        }
+    }
+    image
+}
 
-        to_check.clear();
-        to_check.extend(next_to_check.iter());
+/// Show the removal one wave at a time, red for the rolls going in that wave, or write each
+/// frame to an image in a directory
+pub fn animate_tool(args: &[String]) -> Result<(), String> {
+    let (mut delay, mut scale) = (Duration::from_millis(200), 4);
+    let (mut frames, mut bitmap) = (None, false);
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or_else(|| format!("'{arg}' needs a value"));
+        let number = |value: &String| {
+            value
+                .parse::<usize>()
+                .ok()
+                .filter(|&value| value > 0)
+                .ok_or_else(|| format!("'{arg}' needs a positive number"))
+        };
+        match arg.as_str() {
+            "--delay" => {
+                let millis = value()?
+                    .parse()
+                    .map_err(|_| format!("'{arg}' needs a number of milliseconds"))?;
+                delay = Duration::from_millis(millis);
+            }
+            "--scale" => scale = number(value()?)?,
+            "--frames" => frames = Some(PathBuf::from(value()?)),
+            "--pbm" => bitmap = true,
+            _ => return Err(format!("unknown option '{arg}'")),
+        }
+    }
+
+    let input = crate::config::get().input(4)?;
+    let grid = Grid::<Cell>::parse(&input)?;
+    let erosion = erode(&grid, &configured());
+    let last = erosion.counts.len() + 1;
+
+    if let Some(dir) = frames {
+        fs::create_dir_all(&dir)
+            .map_err(|err| format!("unable to create {}: {err}", dir.display()))?;
+        let extension = if bitmap { "pbm" } else { "ppm" };
+        // a bitmap already shows the rolls going in a wave as gone, so the frame after the last
+        // wave would repeat the one before it
+        let end = if bitmap { last - 1 } else { last };
+        for wave in 0..=end {
+            let frame = frame(&grid, &erosion.waves, wave);
+            let image = if bitmap {
+                pbm(&frame, scale)
+            } else {
+                ppm(&frame, scale)
+            };
+            let path = dir.join(format!("frame{wave:04}.{extension}"));
+            fs::write(&path, image)
+                .map_err(|err| format!("unable to write {}: {err}", path.display()))?;
+        }
+        println!("wrote {} frames to {}", end + 1, dir.display());
+        return Ok(());
     }
 
-    removed_paper
+    let mut stdout = io::stdout().lock();
+    for wave in 0..=last {
+        let frame = frame(&grid, &erosion.waves, wave);
+        // move to the top left and clear the screen before each frame
+        let mut text = String::from("\x1b[H\x1b[2J");
+        for row in frame.rows() {
+            text.extend(row.iter().map(|stage| stage.ansi()));
+            text.push('\n');
+        }
+        let waves = erosion.counts.len();
+        let status = match wave {
+            0 => format!("{waves} waves to come"),
+            _ if wave == last => format!("{} removed", erosion.counts.iter().sum::<usize>()),
+            _ => format!(
+                "wave {wave} of {waves}, removing {}",
+                erosion.counts[wave - 1]
+            ),
+        };
+        text.push_str(&status);
+        text.push('\n');
+        stdout
+            .write_all(text.as_bytes())
+            .and_then(|()| stdout.flush())
+            .map_err(|err| err.to_string())?;
+        if wave < last {
+            thread::sleep(delay);
+        }
+    }
+
+    Ok(())
 }
 
-fn get_paper_to_move(grid: &Grid) -> HashSet<(usize, usize)> {
-    let mut paper_to_move = HashSet::new();
+/// Paper rolls packed one bit per cell, with each row starting a new word
+struct BitGrid {
+    height: usize,
+    /// Words in each row
+    stride: usize,
+    words: Vec<u64>,
+}
 
-    for y in 0..grid.height {
-        for x in 0..grid.width {
-            let current = grid.get(x, y);
-            if current == Cell::Empty {
-                continue;
+impl BitGrid {
+    fn new(grid: &Grid<Cell>) -> Self {
+        let stride = grid.width().div_ceil(64);
+        let mut words = vec![0; stride * grid.height()];
+        for ((x, y), &cell) in grid.iter() {
+            if cell == Cell::Paper {
+                words[y * stride + x / 64] |= 1 << (x % 64);
             }
+        }
+        BitGrid {
+            height: grid.height(),
+            stride,
+            words,
+        }
+    }
 
-            let adjacent_paper = grid
-                .neighbors(x, y)
-                .map(|(x, y)| grid.get(x, y))
-                .filter(|cell| *cell == Cell::Paper)
-                .count();
+    /// The word at `i` in row `y` and its copies with each bit holding the cell to its west and
+    /// to its east. Rows past the edges are empty.
+    fn spread(&self, y: Option<usize>, i: usize) -> [u64; 3] {
+        let Some(y) = y.filter(|&y| y < self.height) else {
+            return [0; 3];
+        };
+        let row = &self.words[y * self.stride..(y + 1) * self.stride];
+        let word = row[i];
+        let before = if i > 0 { row[i - 1] >> 63 } else { 0 };
+        let after = row.get(i + 1).map_or(0, |next| next << 63);
+        [(word << 1) | before, word, (word >> 1) | after]
+    }
 
-            if adjacent_paper < 4 {
-                paper_to_move.insert((x, y));
+    /// The bits of the word at `i` in row `y` whose cells have fewer than `threshold` rolls
+    /// around them
+    fn sparse(&self, y: usize, i: usize, rules: &Rules) -> u64 {
+        let above = self.spread(y.checked_sub(1), i);
+        let [west, _, east] = self.spread(Some(y), i);
+        let below = self.spread(Some(y + 1), i);
+
+        let mut counter = Counter::default();
+        match rules.neighbourhood {
+            Neighbourhood::VonNeumann => {
+                for word in [above[1], west, east, below[1]] {
+                    counter.add(word);
+                }
+            }
+            Neighbourhood::Moore => {
+                for word in above.into_iter().chain(below).chain([west, east]) {
+                    counter.add(word);
+                }
             }
         }
+        counter.less_than(rules.threshold)
     }
+}
 
-    paper_to_move
+/// 64 counts from 0 to 15 side by side, with plane `k` holding bit `k` of each
+#[derive(Default)]
+struct Counter {
+    planes: [u64; 4],
+}
+
+impl Counter {
+    /// Add one to each count whose bit is set, as a ripple-carry adder across the planes
+    fn add(&mut self, word: u64) {
+        let mut carry = word;
+        for plane in &mut self.planes {
+            let next = *plane & carry;
+            *plane ^= carry;
+            carry = next;
+        }
+    }
+
+    /// Bits set for the counts below `threshold`, comparing from the top plane down
+    fn less_than(&self, threshold: usize) -> u64 {
+        if threshold >= 1 << self.planes.len() {
+            return !0;
+        }
+        let (mut less, mut equal) = (0, !0);
+        for (k, &plane) in self.planes.iter().enumerate().rev() {
+            if threshold >> k & 1 == 1 {
+                less |= equal & !plane;
+                equal &= plane;
+            } else {
+                equal &= !plane;
+            }
+        }
+        less
+    }
+}
+
+/// Rolls removed in each of up to `waves` waves, like [`erode`] but working on 64 cells at a
+/// time. Only the puzzle's radius of one without wrapping is supported.
+fn erode_bits(grid: &Grid<Cell>, rules: &Rules, waves: usize) -> Result<Vec<usize>, String> {
+    if rules.radius != 1 || rules.wrap {
+        return Err("the bitset variant only handles a radius of 1 without wrapping".to_string());
+    }
+
+    let mut bits = BitGrid::new(grid);
+    let (height, stride) = (bits.height, bits.stride);
+    let mut removed = vec![0; bits.words.len()];
+    let mut counts = Vec::new();
+    // only rows next to one that lost a roll need looking at again
+    let mut dirty = vec![true; height];
+
+    while counts.len() < waves {
+        let mut changed = vec![false; height];
+        let mut count = 0;
+        for y in (0..height).filter(|&y| dirty[y]) {
+            for i in 0..stride {
+                let index = y * stride + i;
+                removed[index] = bits.words[index] & bits.sparse(y, i, rules);
+                count += removed[index].count_ones() as usize;
+                changed[y] |= removed[index] != 0;
+            }
+        }
+        if count == 0 {
+            break;
+        }
+        counts.push(count);
+
+        // only remove once the whole wave is known, as each roll is judged on the grid at its start
+        for y in (0..height).filter(|&y| changed[y]) {
+            let row = y * stride..(y + 1) * stride;
+            for (word, removed) in bits.words[row.clone()].iter_mut().zip(&removed[row]) {
+                *word &= !removed;
+            }
+        }
+        dirty = (0..height)
+            .map(|y| changed[y.saturating_sub(1)..=(y + 1).min(height - 1)].contains(&true))
+            .collect();
+    }
+
+    Ok(counts)
+}
+
+/// Time removing every roll that can be from a generated grid, cell by cell and with bitsets
+pub fn compare_tool(args: &[String]) -> Result<(), String> {
+    let (mut size, mut density) = (1000, 60);
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .and_then(|value| value.parse::<usize>().ok())
+                .filter(|&value| value > 0)
+                .ok_or_else(|| format!("'{arg}' needs a positive number"))
+        };
+        match arg.as_str() {
+            "--size" => size = value()?,
+            "--density" => density = value()?,
+            _ => return Err(format!("unknown option '{arg}'")),
+        }
+    }
+    if density > 100 {
+        return Err("'--density' is a percentage, so can't be more than 100".to_string());
+    }
+
+    let grid = parse_grid(&generate_grid(size, size, density));
+    let rules = configured();
+    let iterations = crate::config::get().iterations.value;
+    let (cell_counts, cells) = bench::time(iterations, || erode(&grid, &rules).counts);
+    let (bit_counts, bits) = bench::time(iterations, || erode_bits(&grid, &rules, usize::MAX));
+    let bit_counts = bit_counts?;
+    if cell_counts != bit_counts {
+        return Err("the two implementations removed rolls in different waves".to_string());
+    }
+
+    println!(
+        "{size}x{size} grid, {density}% paper, {} rolls removed in {} waves",
+        cell_counts.iter().sum::<usize>(),
+        cell_counts.len()
+    );
+    bench::print_comparison(("bitset", bits), ("cells", cells));
+
+    Ok(())
+}
+
+/// A grid where each cell is a paper roll with a chance of `density` percent
+fn generate_grid(width: usize, height: usize, density: usize) -> String {
+    let mut lcg = Lcg::new(12345);
+    let mut input = String::with_capacity((width + 1) * height);
+    for _ in 0..height {
+        for _ in 0..width {
+            let paper = lcg.below(100) < density as u64;
+            input.push(if paper { '@' } else { '.' });
+        }
+        input.push('\n');
+    }
+    input
 }
 
 #[cfg(test)]
@@ -171,11 +678,241 @@ mod tests {
 
     #[test]
     fn example1() {
-        assert_eq!(solution1(INPUT), 13);
+        assert_eq!(solution1(INPUT, PUZZLE), 13);
     }
 
     #[test]
     fn example2() {
-        assert_eq!(solution2(INPUT), 43);
+        assert_eq!(solution2(INPUT, PUZZLE), 43);
+    }
+
+    #[test]
+    fn removal_waves() {
+        let grid = parse_grid(INPUT);
+        let erosion = erode(&grid, &PUZZLE);
+        assert_eq!(erosion.counts, [13, 12, 7, 5, 2, 1, 1, 1, 1]);
+
+        let annotated = annotate(&grid, &erosion.waves).to_string();
+        assert_eq!(
+            annotated,
+            "..11.1121.\n\
+             134.2.2.32\n\
+             24578.1.33\n\
+             2.69@@..2.\n\
+             13.@@@@.21\n\
+             .24@@@@@.2\n\
+             .2.@.@.@@3\n\
+             1.4@@.@@@4\n\
+             .23@@@@@5.\n\
+             1.1.@@@.1.\n"
+        );
+        assert_eq!(wave_mark(10), 'a');
+        assert_eq!(wave_mark(40), '+');
+    }
+
+    /// Remove rolls a whole wave at a time, recounting every roll's neighbours each wave
+    fn erode_slowly(grid: &Grid<Cell>, rules: &Rules) -> Vec<usize> {
+        let neighbours = Neighbours::new(rules, grid);
+        let mut grid = grid.clone();
+        let mut counts = Vec::new();
+        loop {
+            let removed: Vec<_> = grid
+                .iter()
+                .filter(|&(position, &cell)| {
+                    cell == Cell::Paper && neighbours.paper(&grid, position) < rules.threshold
+                })
+                .map(|(position, _)| position)
+                .collect();
+            if removed.is_empty() {
+                return counts;
+            }
+            counts.push(removed.len());
+            for position in removed {
+                grid[position] = Cell::Empty;
+            }
+        }
+    }
+
+    #[test]
+    fn neighbourhood_shapes() {
+        let rules = |neighbourhood, radius| Rules {
+            neighbourhood,
+            radius,
+            ..PUZZLE
+        };
+        assert_eq!(rules(Neighbourhood::VonNeumann, 1).offsets().len(), 4);
+        assert_eq!(rules(Neighbourhood::Moore, 1).offsets().len(), 8);
+        assert_eq!(rules(Neighbourhood::VonNeumann, 2).offsets().len(), 12);
+        assert_eq!(rules(Neighbourhood::Moore, 2).offsets().len(), 24);
+    }
+
+    #[test]
+    fn wrapping_edges() {
+        let block = parse_grid("@@@\n@@@\n@@@\n");
+        // bounded, the corners have three neighbours and go first, then the edges, then the centre
+        assert_eq!(erode(&block, &PUZZLE).counts, [4, 4, 1]);
+        // wrapped, every roll has eight neighbours
+        let wrap = Rules {
+            wrap: true,
+            ..PUZZLE
+        };
+        assert_eq!(erode(&block, &wrap).counts, []);
+
+        let line = parse_grid("@@@@@\n");
+        let rules = Rules {
+            threshold: 2,
+            neighbourhood: Neighbourhood::VonNeumann,
+            ..PUZZLE
+        };
+        assert_eq!(erode(&line, &rules).counts, [2, 2, 1]);
+        // wrapped, the row's ends meet, and the rows above and below are the roll itself, which
+        // isn't its own neighbour
+        assert_eq!(
+            erode(
+                &line,
+                &Rules {
+                    wrap: true,
+                    ..rules
+                }
+            )
+            .counts,
+            []
+        );
+
+        // on a wrapped 2x2 grid the eight offsets only reach the three other rolls
+        let square = parse_grid("@@\n@@\n");
+        assert_eq!(Neighbours::new(&wrap, &square).paper(&square, (0, 0)), 3);
+        assert_eq!(erode(&square, &wrap).counts, [4]);
+    }
+
+    #[test]
+    fn rules_match_slow_erosion() {
+        let grid = parse_grid(INPUT);
+        for neighbourhood in [Neighbourhood::VonNeumann, Neighbourhood::Moore] {
+            for radius in 1..=3 {
+                for threshold in [1, 2, 3, 4, 6, 9] {
+                    for wrap in [false, true] {
+                        let rules = Rules {
+                            threshold,
+                            neighbourhood,
+                            radius,
+                            wrap,
+                        };
+                        let erosion = erode(&grid, &rules);
+                        assert_eq!(erosion.counts, erode_slowly(&grid, &rules), "{rules:?}");
+                        assert_eq!(
+                            solution1(INPUT, rules),
+                            erosion.counts.first().copied().unwrap_or(0),
+                            "{rules:?}"
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn bitset_matches_cells() {
+        let check = |grid: &Grid<Cell>| {
+            for neighbourhood in [Neighbourhood::VonNeumann, Neighbourhood::Moore] {
+                for threshold in 1..=9 {
+                    let rules = Rules {
+                        threshold,
+                        neighbourhood,
+                        ..PUZZLE
+                    };
+                    let counts = erode(grid, &rules).counts;
+                    assert_eq!(erode_bits(grid, &rules, usize::MAX), Ok(counts.clone()));
+                    assert_eq!(
+                        erode_bits(grid, &rules, 1),
+                        Ok(counts.into_iter().take(1).collect())
+                    );
+                }
+            }
+        };
+
+        check(&parse_grid(INPUT));
+        // widths either side of the word boundaries
+        for width in [1, 63, 64, 65, 127, 128, 200] {
+            for density in [30, 60, 90] {
+                check(&parse_grid(&generate_grid(width, 40, density)));
+            }
+        }
+    }
+
+    #[test]
+    fn bitset_variants() {
+        assert_eq!(part1_bitset(INPUT).unwrap(), "13");
+        assert_eq!(part2_bitset(INPUT).unwrap(), "43");
+        let wrap = Rules {
+            wrap: true,
+            ..PUZZLE
+        };
+        assert_eq!(
+            erode_bits(&parse_grid(INPUT), &wrap, 1),
+            Err("the bitset variant only handles a radius of 1 without wrapping".to_string())
+        );
+    }
+
+    #[test]
+    fn counter() {
+        let mut counter = Counter::default();
+        for word in [0b1111, 0b1110, 0b1100, 0b1000] {
+            counter.add(word);
+        }
+        // counts of 1, 2, 3 and 4 from the lowest bit up, and 0 above
+        assert_eq!(counter.less_than(0), 0);
+        assert_eq!(counter.less_than(3) & 0b11111, 0b10011);
+        assert_eq!(counter.less_than(5) & 0b11111, 0b11111);
+        assert_eq!(counter.less_than(16), !0);
+    }
+
+    #[test]
+    fn animation_frames() {
+        let grid = parse_grid(INPUT);
+        let erosion = erode(&grid, &PUZZLE);
+        let stages = |wave| {
+            let frame = frame(&grid, &erosion.waves, wave);
+            [Stage::Paper, Stage::Removing, Stage::Removed]
+                .map(|stage| frame.cells().iter().filter(|&&cell| cell == stage).count())
+        };
+        assert_eq!(stages(0), [71, 0, 0]);
+        assert_eq!(stages(1), [58, 13, 0]);
+        assert_eq!(stages(2), [46, 12, 13]);
+        assert_eq!(stages(10), [28, 0, 43]);
+
+        // in a bitmap the last wave looks the same as the end, so that frame is left out
+        let last = erosion.counts.len() + 1;
+        let bitmap = |wave| pbm(&frame(&grid, &erosion.waves, wave), 1);
+        assert_eq!(bitmap(last - 1), bitmap(last));
+        assert_ne!(bitmap(last - 2), bitmap(last - 1));
+    }
+
+    #[test]
+    fn images() {
+        let grid = parse_grid("@.\n.@\n");
+        let frame = frame(&grid, &grid.map(|_, _| None), 0);
+
+        let image = ppm(&frame, 2);
+        let header = b"P6\n4 4\n255\n";
+        assert_eq!(&image[..header.len()], header);
+        assert_eq!(image.len(), header.len() + 4 * 4 * 3);
+        let pixel = |x: usize, y: usize| &image[header.len() + (y * 4 + x) * 3..][..3];
+        assert_eq!(pixel(1, 1), Stage::Paper.rgb());
+        assert_eq!(pixel(2, 1), Stage::Empty.rgb());
+        assert_eq!(pixel(3, 3), Stage::Paper.rgb());
+
+        let image = pbm(&frame, 2);
+        let header = b"P4\n4 4\n";
+        assert_eq!(&image[..header.len()], header);
+        assert_eq!(
+            &image[header.len()..],
+            [0b1100_0000, 0b1100_0000, 0b0011_0000, 0b0011_0000]
+        );
+    }
+
+    #[test]
+    fn grid_round_trip() {
+        assert_eq!(parse_grid(INPUT).to_string(), INPUT);
     }
 }
@@ -1,13 +1,13 @@
 use std::ops::RangeInclusive;
 
-const INPUT: &str = include_str!("input/day5.txt");
+use super::intervals::merge;
 
-pub fn part1() {
-    println!("{}", solution1(INPUT));
+pub fn part1(input: &str) -> Result<String, String> {
+    Ok(solution1(input).to_string())
 }
 
-pub fn part2() {
-    println!("{}", solution2(INPUT));
+pub fn part2(input: &str) -> Result<String, String> {
+    Ok(solution2(input).to_string())
 }
 
 fn parse_ranges<'a>(lines: impl Iterator<Item = &'a str>) -> Vec<RangeInclusive<u64>> {
@@ -41,36 +41,11 @@ fn solution1(input: &str) -> usize {
 fn solution2(input: &str) -> u64 {
     let mut lines = input.lines();
     let range_section = lines.by_ref().take_while(|line| !line.is_empty());
-    let mut ranges = parse_ranges(range_section);
-
-    ranges.sort_by_key(|range| *range.start());
-
-    // check each range to see if it overlaps and combine them if they do
-    // since they are sorted by the start value, one pass is sufficient to
-    // remove all overlaps
-    let mut non_overlapping_ranges: Vec<RangeInclusive<u64>> = Vec::new();
-    
-    'outer: for range in ranges.into_iter() {
-        // check to see if the range overlaps preceding ranges
-        for existing in non_overlapping_ranges.iter_mut() {
-            if existing.contains(range.start()) {
-                if range.end() > existing.end() {
-                    *existing = *existing.start()..=*range.end();
-                }
-                continue 'outer;
-            } else if existing.contains(range.end()) {
-                if range.start() < existing.start() {
-                    *existing = *range.start()..=*existing.end();
-                }
-                continue 'outer;
-            }
-        }
-        // no overlap, so add it to the list
-        non_overlapping_ranges.push(range);
-    }
+    let ranges = parse_ranges(range_section);
 
-    // sum the size of each range
-    non_overlapping_ranges
+    // sum the size of each range once overlaps are combined
+    merge(ranges)
+        .ranges
         .into_iter()
         .map(|r| r.end() - r.start() + 1)
         .sum()
@@ -1,11 +1,9 @@
-const INPUT: &str = include_str!("input/day6.txt");
-
-pub fn part1() {
-    println!("{}", solution1(INPUT));
+pub fn part1(input: &str) -> Result<String, String> {
+    Ok(solution1(input).to_string())
 }
 
-pub fn part2() {
-    println!("{}", solution2(INPUT));
+pub fn part2(input: &str) -> Result<String, String> {
+    Ok(solution2(input).to_string())
 }
 
 fn parse_ltr_numbers<'a>(input: impl Iterator<Item = &'a str>) -> (usize, Vec<u64>) {
@@ -91,7 +89,7 @@ fn solution1(input: &str) -> u64 {
 
     let problems = numbers.chunks_exact(problem_length);
     problems
-        .zip(ops.into_iter())
+        .zip(ops)
         .map(|(numbers, op)| numbers.iter().copied().reduce(op).unwrap())
         .sum()
 }
@@ -105,7 +103,7 @@ fn solution2(input: &str) -> u64 {
 
     numbers
         .iter()
-        .zip(ops.into_iter())
+        .zip(ops)
         .map(|(numbers, op)| numbers.iter().copied().reduce(op).unwrap())
         .sum()
 }
@@ -1,11 +1,9 @@
-const INPUT: &str = include_str!("input/day7.txt");
-
-pub fn part1() {
-    println!("{}", solution1(INPUT));
+pub fn part1(input: &str) -> Result<String, String> {
+    Ok(solution1(input).to_string())
 }
 
-pub fn part2() {
-    println!("{}", solution2(INPUT));
+pub fn part2(input: &str) -> Result<String, String> {
+    Ok(solution2(input).to_string())
 }
 
 fn parse_manifold(input: &str) -> Vec<Vec<usize>> {
@@ -1,13 +1,11 @@
 use std::{cmp::Reverse, collections::BinaryHeap};
 
-const INPUT: &str = include_str!("input/day8.txt");
-
-pub fn part1() {
-    println!("{}", solution1::<1000>(INPUT));
+pub fn part1(input: &str) -> Result<String, String> {
+    Ok(solution1(input, crate::config::get().day8_connections.value).to_string())
 }
 
-pub fn part2() {
-    println!("{}", solution2(INPUT));
+pub fn part2(input: &str) -> Result<String, String> {
+    Ok(solution2(input).to_string())
 }
 
 fn parse_points(input: &str) -> Vec<[i64; 3]> {
@@ -66,7 +64,7 @@ impl UnionFind {
     }
 }
 
-fn solution1<const N: usize>(input: &str) -> usize {
+fn solution1(input: &str, connections: usize) -> usize {
     let points = parse_points(input);
     let n = points.len();
 
@@ -75,8 +73,7 @@ fn solution1<const N: usize>(input: &str) -> usize {
     let mut closest_pairs = BinaryHeap::with_capacity(n * (n - 1) / 2);
     for i in 0..n - 1 {
         let [cx, cy, cz] = points[i];
-        for j in i + 1..n {
-            let [x, y, z] = points[j];
+        for (j, &[x, y, z]) in points.iter().enumerate().skip(i + 1) {
             let dist_sq = (cx - x).pow(2) + (cy - y).pow(2) + (cz - z).pow(2);
             closest_pairs.push((Reverse(dist_sq), i, j));
         }
@@ -84,8 +81,8 @@ fn solution1<const N: usize>(input: &str) -> usize {
 
     let mut uf = UnionFind::new(n);
 
-    // process only the N closest pairs
-    for _ in 0..N {
+    // process only the closest pairs, up to the number of connections
+    for _ in 0..connections {
         if let Some((_, i, j)) = closest_pairs.pop() {
             uf.union(i, j);
         }
@@ -105,8 +102,7 @@ fn solution2(input: &str) -> i64 {
     let mut pairs = Vec::with_capacity(n * (n - 1) / 2);
     for i in 0..n - 1 {
         let [cx, cy, cz] = points[i];
-        for j in i + 1..n {
-            let [x, y, z] = points[j];
+        for (j, &[x, y, z]) in points.iter().enumerate().skip(i + 1) {
             let dist_sq = (cx - x).pow(2) + (cy - y).pow(2) + (cz - z).pow(2);
             pairs.push((dist_sq, i, j));
         }
@@ -134,7 +130,7 @@ mod tests {
 
     #[test]
     fn example1() {
-        assert_eq!(solution1::<10>(INPUT), 40);
+        assert_eq!(solution1(INPUT, 10), 40);
     }
 
     #[test]
@@ -1,11 +1,9 @@
-const INPUT: &str = include_str!("input/day9.txt");
-
-pub fn part1() {
-    println!("{}", solution1(INPUT));
+pub fn part1(input: &str) -> Result<String, String> {
+    Ok(solution1(input).to_string())
 }
 
-pub fn part2() {
-    println!("{}", solution2(INPUT));
+pub fn part2(input: &str) -> Result<String, String> {
+    Ok(solution2(input).to_string())
 }
 
 fn parse_points(input: &str) -> Vec<[i64; 2]> {
@@ -25,8 +23,7 @@ fn solution1(input: &str) -> i64 {
     let mut areas = Vec::with_capacity(n * (n - 1) / 2);
     for i in 0..n {
         let [ax, ay] = points[i];
-        for j in i + 1..n {
-            let [bx, by] = points[j];
+        for &[bx, by] in &points[i + 1..] {
             // have to add one to the length of the sides because we're dealing with tile coordinates and not the
             // actual corners
             let dx = (bx - ax).abs() + 1;
@@ -46,8 +43,7 @@ fn solution2(input: &str) -> i64 {
     let mut rectangles = Vec::with_capacity(n * (n - 1) / 2);
     for i in 0..n {
         let [ax, ay] = points[i];
-        for j in i + 1..n {
-            let [bx, by] = points[j];
+        for &[bx, by] in &points[i + 1..] {
             let dx = (bx - ax).abs() + 1;
             let dy = (by - ay).abs() + 1;
             rectangles.push((dx * dy, Rect::new(ax, ay, bx, by)));
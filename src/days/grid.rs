@@ -0,0 +1,295 @@
+// shared by the grid puzzles, none of which need every part of it
+#![allow(dead_code)]
+
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+/// Offsets to the cells sharing an edge, in reading order
+pub const ORTHOGONAL: [(isize, isize); 4] = [(0, -1), (-1, 0), (1, 0), (0, 1)];
+
+/// Offsets to the cells sharing an edge or a corner, in reading order
+pub const ADJACENT: [(isize, isize); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+/// A cell that can be read from a character of a puzzle input
+pub trait FromChar: Sized {
+    fn from_char(c: char) -> Option<Self>;
+}
+
+impl FromChar for char {
+    fn from_char(c: char) -> Option<Self> {
+        Some(c)
+    }
+}
+
+/// A rectangle of cells stored row by row, indexed by `(x, y)` from the top left
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Grid {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+
+    /// Read a grid with one line per row, which must all be the same length. There must be at
+    /// least one row.
+    pub fn parse(input: &str) -> Result<Self, String>
+    where
+        T: FromChar,
+    {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = Vec::with_capacity(input.len());
+
+        for (y, line) in input.lines().enumerate() {
+            if line.is_empty() {
+                return Err(format!("line {} is empty", y + 1));
+            }
+            let mut length = 0;
+            for (x, c) in line.chars().enumerate() {
+                let cell = T::from_char(c).ok_or_else(|| {
+                    format!(
+                        "invalid character '{c}' at line {}, column {}",
+                        y + 1,
+                        x + 1
+                    )
+                })?;
+                cells.push(cell);
+                length += 1;
+            }
+            if *width.get_or_insert(length) != length {
+                return Err(format!("line {} is a different width to the first", y + 1));
+            }
+            height += 1;
+        }
+
+        let Some(width) = width else {
+            return Err("the grid is empty".to_string());
+        };
+        Ok(Grid {
+            width,
+            height,
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Every cell, row by row
+    pub fn cells(&self) -> &[T] {
+        &self.cells
+    }
+
+    /// Position of a cell in [`Grid::cells`]
+    pub fn index_of(&self, x: usize, y: usize) -> usize {
+        y * self.width + x
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<&T> {
+        (x < self.width && y < self.height).then(|| &self.cells[self.index_of(x, y)])
+    }
+
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
+        if x < self.width && y < self.height {
+            let index = self.index_of(x, y);
+            Some(&mut self.cells[index])
+        } else {
+            None
+        }
+    }
+
+    /// The cell at a position that wraps around the edges, so `(-1, 0)` is the end of the top row.
+    /// Panics if the grid is empty.
+    pub fn get_wrapping(&self, x: isize, y: isize) -> &T {
+        let x = x.rem_euclid(self.width as isize) as usize;
+        let y = y.rem_euclid(self.height as isize) as usize;
+        &self[(x, y)]
+    }
+
+    /// Move from a cell by an offset, if that stays inside the grid
+    pub fn offset(
+        &self,
+        (x, y): (usize, usize),
+        (dx, dy): (isize, isize),
+    ) -> Option<(usize, usize)> {
+        let x = x.checked_add_signed(dx).filter(|&x| x < self.width)?;
+        let y = y.checked_add_signed(dy).filter(|&y| y < self.height)?;
+        Some((x, y))
+    }
+
+    /// Move from a cell by an offset, wrapping around the edges. Panics if the grid is empty.
+    pub fn offset_wrapping(
+        &self,
+        (x, y): (usize, usize),
+        (dx, dy): (isize, isize),
+    ) -> (usize, usize) {
+        let x = (x as isize + dx).rem_euclid(self.width as isize) as usize;
+        let y = (y as isize + dy).rem_euclid(self.height as isize) as usize;
+        (x, y)
+    }
+
+    /// The cells sharing an edge with `(x, y)`, up to 4
+    pub fn neighbours4(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        ORTHOGONAL
+            .into_iter()
+            .filter_map(move |offset| self.offset((x, y), offset))
+    }
+
+    /// The cells sharing an edge or a corner with `(x, y)`, up to 8
+    pub fn neighbours8(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        ADJACENT
+            .into_iter()
+            .filter_map(move |offset| self.offset((x, y), offset))
+    }
+
+    /// Every position, row by row
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> + use<T> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    /// Every cell with its position, row by row
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.positions().zip(&self.cells)
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        self.cells.iter().skip(x).step_by(self.width.max(1))
+    }
+
+    /// A grid of the same size with `f` applied to each cell
+    pub fn map<U>(&self, mut f: impl FnMut((usize, usize), &T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self
+                .iter()
+                .map(|(position, cell)| f(position, cell))
+                .collect(),
+        }
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &T {
+        assert!(
+            x < self.width && y < self.height,
+            "({x}, {y}) is outside the grid"
+        );
+        &self.cells[y * self.width + x]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut T {
+        assert!(
+            x < self.width && y < self.height,
+            "({x}, {y}) is outside the grid"
+        );
+        &mut self.cells[y * self.width + x]
+    }
+}
+
+/// One line per row, with each cell written by its own `Display`
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "abc\ndef\n";
+
+    #[test]
+    fn parse_and_display() {
+        let grid = Grid::<char>::parse(INPUT).unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[(2, 1)], 'f');
+        assert_eq!(grid.to_string(), INPUT);
+
+        assert!(Grid::<char>::parse("abc\nde\n").is_err());
+        assert!(Grid::<char>::parse("abc\n\ndef\n").is_err());
+        assert!(Grid::<char>::parse("").is_err());
+    }
+
+    #[test]
+    fn access() {
+        let grid = Grid::<char>::parse(INPUT).unwrap();
+        assert_eq!(grid.get(1, 0), Some(&'b'));
+        assert_eq!(grid.get(3, 0), None);
+        assert_eq!(grid.get(0, 2), None);
+        assert_eq!(*grid.get_wrapping(-1, -1), 'f');
+        assert_eq!(*grid.get_wrapping(4, 2), 'b');
+
+        let rows: Vec<_> = grid.rows().collect();
+        assert_eq!(rows, [['a', 'b', 'c'], ['d', 'e', 'f']]);
+        assert_eq!(grid.column(1).collect::<String>(), "be");
+
+        let mut grid = grid;
+        *grid.get_mut(0, 1).unwrap() = 'x';
+        assert_eq!(grid.get_mut(0, 2), None);
+        assert_eq!(grid.get_mut(1, usize::MAX), None);
+        assert_eq!(grid.get(usize::MAX, 1), None);
+        assert_eq!(grid.row(1), ['x', 'e', 'f']);
+    }
+
+    #[test]
+    fn neighbourhoods() {
+        let grid = Grid::new(3, 3, 0);
+        assert_eq!(grid.neighbours4(1, 1).count(), 4);
+        assert_eq!(grid.neighbours8(1, 1).count(), 8);
+        assert_eq!(grid.neighbours4(0, 0).collect::<Vec<_>>(), [(1, 0), (0, 1)]);
+        assert_eq!(
+            grid.neighbours8(2, 2).collect::<Vec<_>>(),
+            [(1, 1), (2, 1), (1, 2)]
+        );
+        assert_eq!(grid.offset_wrapping((0, 0), (-1, -1)), (2, 2));
+
+        let sums = grid.map(|(x, y), _| x + y);
+        assert_eq!(sums.to_string(), "012\n123\n234\n");
+    }
+}
@@ -0,0 +1,84 @@
+use num_traits::{CheckedAdd, One};
+use std::ops::RangeInclusive;
+
+/// The union of some inclusive ranges, along with the input ranges that overlapped
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Merged<T> {
+    /// Disjoint ranges in increasing order, with no two adjacent
+    pub ranges: Vec<RangeInclusive<T>>,
+    /// Each input range that shares values with an earlier one, paired with the earlier range
+    /// reaching furthest
+    pub overlaps: Vec<(RangeInclusive<T>, RangeInclusive<T>)>,
+}
+
+/// Combine overlapping and adjacent ranges, e.g. 1-4, 3-6 and 7-9 become 1-9. Empty ranges are
+/// dropped.
+pub fn merge<T>(ranges: impl IntoIterator<Item = RangeInclusive<T>>) -> Merged<T>
+where
+    T: Ord + Copy + CheckedAdd + One,
+{
+    let mut ranges: Vec<_> = ranges
+        .into_iter()
+        .filter(|range| !range.is_empty())
+        .collect();
+    ranges.sort_by_key(|range| (*range.start(), *range.end()));
+
+    let mut merged: Vec<RangeInclusive<T>> = Vec::new();
+    let mut overlaps = Vec::new();
+    // the input range with the largest end so far, which any overlapping range must overlap
+    let mut furthest: Option<RangeInclusive<T>> = None;
+
+    // sorted by start, so each range can only join the last merged one
+    for range in ranges {
+        if let Some(previous) = &furthest
+            && range.start() <= previous.end()
+        {
+            overlaps.push((previous.clone(), range.clone()));
+        }
+        if furthest
+            .as_ref()
+            .is_none_or(|previous| range.end() > previous.end())
+        {
+            furthest = Some(range.clone());
+        }
+
+        match merged.last_mut() {
+            // adjacent when the last one ends just before this starts
+            Some(last)
+                if last
+                    .end()
+                    .checked_add(&T::one())
+                    .is_none_or(|next| *range.start() <= next) =>
+            {
+                if range.end() > last.end() {
+                    *last = *last.start()..=*range.end();
+                }
+            }
+            _ => merged.push(range),
+        }
+    }
+
+    Merged {
+        ranges: merged,
+        overlaps,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn overlapping_and_adjacent() {
+        let merged = merge([7..=9, 3..=6, 1..=4, 20..=25, 21..=22u64]);
+        assert_eq!(merged.ranges, [1..=9, 20..=25]);
+        assert_eq!(merged.overlaps, [(1..=4, 3..=6), (20..=25, 21..=22)]);
+    }
+
+    #[test]
+    fn up_to_the_largest_value() {
+        let merged = merge([u128::MAX - 5..=u128::MAX, 0..=3, u128::MAX..=u128::MAX]);
+        assert_eq!(merged.ranges, [0..=3, u128::MAX - 5..=u128::MAX]);
+        assert_eq!(merged.overlaps.len(), 1);
+    }
+}
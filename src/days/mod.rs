@@ -7,7 +7,7 @@ macro_rules! days {
         )*
         paste! {
             pub const COUNT: usize = [$($n),*].len();
-            pub const SOLUTIONS: [[fn(); 2]; COUNT] = [
+            pub const SOLUTIONS: [[fn(&str) -> Result<String, String>; 2]; COUNT] = [
                 $([ [<day $n>]::part1, [<day $n>]::part2 ], )*
             ];
         }
@@ -15,3 +15,150 @@ macro_rules! days {
 }
 
 days!(1, 2, 3, 4, 5, 6, 7, 8, 9);
+
+mod grid;
+mod intervals;
+mod random;
+
+/// Alternative implementations of a part, run instead of the main one with `--variant NAME`
+pub struct Variant {
+    pub day: usize,
+    pub part: usize,
+    pub name: &'static str,
+    pub solve: fn(&str) -> Result<String, String>,
+}
+
+pub const VARIANTS: &[Variant] = &[
+    Variant {
+        day: 1,
+        part: 1,
+        name: "stream",
+        solve: day1::part1_stream,
+    },
+    Variant {
+        day: 1,
+        part: 2,
+        name: "stream",
+        solve: day1::part2_stream,
+    },
+    Variant {
+        day: 2,
+        part: 1,
+        name: "scan",
+        solve: day2::part1_scan,
+    },
+    Variant {
+        day: 2,
+        part: 2,
+        name: "scan",
+        solve: day2::part2_scan,
+    },
+    Variant {
+        day: 3,
+        part: 1,
+        name: "window",
+        solve: day3::part1_window,
+    },
+    Variant {
+        day: 3,
+        part: 2,
+        name: "window",
+        solve: day3::part2_window,
+    },
+    Variant {
+        day: 4,
+        part: 1,
+        name: "bitset",
+        solve: day4::part1_bitset,
+    },
+    Variant {
+        day: 4,
+        part: 2,
+        name: "bitset",
+        solve: day4::part2_bitset,
+    },
+];
+
+/// Checks on a day's input that don't stop it being solved but are worth pointing out. The
+/// runner prints their warnings before running the day's parts.
+pub struct Check {
+    pub day: usize,
+    pub warnings: fn(&str) -> Vec<String>,
+}
+
+pub const CHECKS: &[Check] = &[Check {
+    day: 2,
+    warnings: day2::warnings,
+}];
+
+/// Extra ways of examining a day's input beyond its answers, run with `aoc2025 tool DAY NAME`.
+/// Tools are given the arguments following their name and read the input themselves.
+pub struct Tool {
+    pub day: usize,
+    pub name: &'static str,
+    pub about: &'static str,
+    pub run: fn(&[String]) -> Result<(), String>,
+}
+
+pub const TOOLS: &[Tool] = &[
+    Tool {
+        day: 1,
+        name: "trace",
+        about: "each rotation's positions and target crossings [--csv] [--brute-force]",
+        run: day1::trace_tool,
+    },
+    Tool {
+        day: 1,
+        name: "visits",
+        about: "distance turned and most and least visited positions [--histogram]",
+        run: day1::visits_tool,
+    },
+    Tool {
+        day: 1,
+        name: "starts",
+        about: "start positions giving the fewest and most target hits [--all]",
+        run: day1::starts_tool,
+    },
+    Tool {
+        day: 1,
+        name: "stream",
+        about: "solve a rotation log of any size with constant memory [PATH|-] [--bench]",
+        run: day1::stream_tool,
+    },
+    Tool {
+        day: 2,
+        name: "repeats",
+        about: "repeated IDs by pattern length and repeats [--times K] [--period P] [--list]",
+        run: day2::repeats_tool,
+    },
+    Tool {
+        day: 3,
+        name: "compare",
+        about: "time the stack and window selections on generated banks [--banks N] [--length L] [--digits D]",
+        run: day3::compare_tool,
+    },
+    Tool {
+        day: 3,
+        name: "show",
+        about: "each bank with its chosen batteries highlighted [--digits N] [--plain]",
+        run: day3::show_tool,
+    },
+    Tool {
+        day: 4,
+        name: "waves",
+        about: "paper rolls removed in each wave and the grid marked with when",
+        run: day4::waves_tool,
+    },
+    Tool {
+        day: 4,
+        name: "compare",
+        about: "time the bitset removal against the cell by cell one [--size N] [--density PERCENT]",
+        run: day4::compare_tool,
+    },
+    Tool {
+        day: 4,
+        name: "animate",
+        about: "watch the rolls removed wave by wave [--delay MS] [--frames DIR] [--pbm] [--scale N]",
+        run: day4::animate_tool,
+    },
+];
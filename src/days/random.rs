@@ -0,0 +1,37 @@
+/// A small seeded pseudo-random generator, for generated inputs and tests that need the same
+/// numbers on every run
+pub struct Lcg {
+    state: u64,
+}
+
+impl Lcg {
+    pub fn new(seed: u64) -> Self {
+        Lcg { state: seed }
+    }
+
+    /// The next number from 0 up to but not including `bound`
+    pub fn below(&mut self, bound: u64) -> u64 {
+        self.state = self
+            .state
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        // the low bits of an LCG repeat quickly, so only the high ones are used
+        (self.state >> 33) % bound
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn seeded() {
+        let numbers = |seed| {
+            let mut lcg = Lcg::new(seed);
+            (0..100).map(|_| lcg.below(10)).collect::<Vec<_>>()
+        };
+        assert_eq!(numbers(1), numbers(1));
+        assert_ne!(numbers(1), numbers(2));
+        assert!(numbers(1).iter().all(|&n| n < 10));
+    }
+}
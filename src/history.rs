@@ -0,0 +1,80 @@
+use std::collections::HashMap;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::config;
+
+/// Median timings from every bench and report run, one `TIMESTAMP DAY PART NANOS` line per part,
+/// kept in the target directory
+pub const FILE_NAME: &str = "aoc-history.tsv";
+
+/// Append the medians from a run to the history
+pub fn record(medians: &[(usize, usize, Duration)]) {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|since| since.as_secs())
+        .unwrap_or_default();
+
+    let mut lines = String::new();
+    for (day, part, median) in medians {
+        lines.push_str(&format!(
+            "{timestamp}\t{day}\t{part}\t{}\n",
+            median.as_nanos()
+        ));
+    }
+
+    let path = config::target_file(FILE_NAME);
+    let written = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .and_then(|mut file| file.write_all(lines.as_bytes()));
+    if let Err(err) = written {
+        eprintln!(
+            "Unable to record timing history in {}: {err}",
+            path.display()
+        );
+    }
+}
+
+/// Load the recorded medians for each day and part, oldest first
+pub fn load() -> HashMap<(usize, usize), Vec<Duration>> {
+    parse(&fs::read_to_string(config::target_file(FILE_NAME)).unwrap_or_default())
+}
+
+fn parse(text: &str) -> HashMap<(usize, usize), Vec<Duration>> {
+    let mut history: HashMap<_, Vec<_>> = HashMap::new();
+
+    for line in text.lines() {
+        let fields: Vec<_> = line.split('\t').collect();
+        let [_, day, part, nanos] = fields[..] else {
+            continue;
+        };
+        let (Ok(day), Ok(part), Ok(nanos)) = (day.parse(), part.parse(), nanos.parse()) else {
+            continue;
+        };
+        history
+            .entry((day, part))
+            .or_default()
+            .push(Duration::from_nanos(nanos));
+    }
+
+    history
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parsing() {
+        let history = parse("100\t1\t1\t2000\n100\t1\t2\t5000\nbad line\n200\t1\t1\t1500\n");
+        assert_eq!(
+            history[&(1, 1)],
+            [Duration::from_nanos(2000), Duration::from_nanos(1500)]
+        );
+        assert_eq!(history[&(1, 2)], [Duration::from_nanos(5000)]);
+        assert_eq!(history.len(), 2);
+    }
+}
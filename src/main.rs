@@ -1,62 +1,490 @@
 use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
+use answers::Answers;
+use cache::Cache;
+use config::{Config, Format};
+use runner::Outcome;
+
+mod allocations;
+mod answers;
+mod bench;
+mod cache;
+mod config;
 mod days;
+mod history;
+mod report;
+mod runner;
+mod tui;
+
+#[global_allocator]
+static ALLOCATOR: allocations::Counting = allocations::Counting;
 
 /**
- * Usage: aoc2025 [DAY] [PART]
+ * Usage: aoc2025 [COMMAND] [OPTIONS] [DAY] [PART]
  * Run the solutions for Advent of Code 2025.
  *
+ * [COMMAND]  run (default), bench to time repeated runs, report to summarise a bench run, tui
+ *            for an interactive view of every day, tool to run one of a day's extra tools, or
+ *            config to show the settings in effect
  * [DAY]   Run provided solutions for the specified day
  * [PART]  Run only the specified part
+ *
+ * Settings are read from aoc.toml in the working directory and can be overridden with:
+ * --config <PATH>          Read settings from PATH instead of aoc.toml
+ * --input-dir <DIR>        Directory containing the day1.txt..day9.txt puzzle inputs
+ * --timeout <SECS>         Give up on a part after SECS seconds, 0 for no limit
+ * --format <text|json>     Output format for run
+ * --cache                  Reuse answers from earlier runs when the input and program are unchanged
+ * --no-cache               Recompute every answer, even if the cache is enabled in aoc.toml
+ * --iterations <N>         Number of runs of each part for bench
+ * --variant <NAME>         Run a day's alternative implementation instead, for the parts that
+ *                          have one
+ * --day1-size <N>          Number of positions on the day 1 dial
+ * --day1-start <POS>       Position the day 1 dial starts at
+ * --day1-target <POS>      Position the day 1 dial counts visits to
+ * --day2-radix <N>         Radix, from 2 to 36, day 2 writes IDs in when looking for repeats
+ * --day2-lenient           Skip anything in the day 2 input that isn't a range instead of
+ *                          reporting it
+ * --day4-threshold <N>     Day 4 removes paper rolls with fewer than N neighbouring rolls
+ * --day4-neighbourhood <von-neumann|moore>
+ *                          Whether day 4 counts diagonal neighbours
+ * --day4-radius <N>        How many steps away, up to 50, day 4 looks for neighbours
+ * --day4-wrap              Treat the day 4 grid as wrapping around at its edges
+ * --day8-connections <N>   Number of closest pairs day 8 part 1 connects
+ *
+ * Options for report:
+ * --markdown               Print a markdown table of the timings and whether each answer is correct
+ * --readme <PATH>          Write the table into PATH between the timings:start and timings:end
+ *                          comments instead of printing it
+ * --html <PATH>            Write a self-contained page of the timings, with charts of each day and
+ *                          of the timing history, to PATH
+ *
+ * Usage: aoc2025 tool [OPTIONS] [DAY] [NAME] [TOOL OPTIONS]
+ * Run the named tool on the day's input, passing on any arguments after the name. Without a day
+ * and name, lists the available tools.
+ *
+ * Each bench and report run adds its median timings to the history kept in aoc-history.tsv, and the
+ * answer cache is kept in aoc-cache.tsv, both in $CARGO_TARGET_DIR or else target in the working
+ * directory.
  */
 fn main() {
     // Get the command line arguments, skipping the program name
-    let mut arguments = env::args().skip(1);
-    let day = arguments.next();
-    let part = arguments.next();
-
-    if let Some(day) = day {
-        // Parse and validate inputs
-        let Ok(day @ 1..=days::COUNT) = day.parse::<usize>() else {
-            eprintln!(
-                "Invalid input '{day}' for day. Must be between 1 and {}.",
-                days::COUNT
-            );
+    let mut arguments = env::args().skip(1).peekable();
+    let command = match arguments.peek().map(String::as_str) {
+        Some(command @ ("run" | "bench" | "report" | "tui" | "tool" | "config")) => {
+            let command = command.to_string();
+            arguments.next();
+            command
+        }
+        _ => "run".to_string(),
+    };
+
+    // Separate the options from the day and part
+    let mut config_path = PathBuf::from(config::FILE_NAME);
+    let mut markdown = false;
+    let mut readme = None;
+    let mut html = None;
+    let mut variant = None;
+    let mut flags = Vec::new();
+    let mut positional = Vec::new();
+    let mut tool_args = Vec::new();
+    while let Some(argument) = arguments.next() {
+        // everything after the tool's name belongs to the tool
+        if command == "tool" && positional.len() == 2 {
+            tool_args.push(argument);
+            continue;
+        }
+        let Some(name) = argument.strip_prefix("--") else {
+            positional.push(argument);
+            continue;
+        };
+        // switches that don't take a value
+        match name {
+            "cache" | "no-cache" => {
+                flags.push(("cache".to_string(), (name == "cache").to_string()));
+                continue;
+            }
+            "day2-lenient" => {
+                flags.push(("day2.lenient".to_string(), "true".to_string()));
+                continue;
+            }
+            "day4-wrap" => {
+                flags.push(("day4.wrap".to_string(), "true".to_string()));
+                continue;
+            }
+            "markdown" => {
+                markdown = true;
+                continue;
+            }
+            _ => {}
+        }
+        let Some(value) = arguments.next() else {
+            eprintln!("Missing value for option '{argument}'.");
+            return;
+        };
+        let key = match name {
+            "config" => {
+                config_path = PathBuf::from(value);
+                continue;
+            }
+            "readme" => {
+                readme = Some(PathBuf::from(value));
+                continue;
+            }
+            "html" => {
+                html = Some(PathBuf::from(value));
+                continue;
+            }
+            "variant" => {
+                variant = Some(value);
+                continue;
+            }
+            "input-dir" => "input_dir",
+            "timeout" => "timeout",
+            "format" => "format",
+            "iterations" => "bench.iterations",
+            "day1-size" => "day1.size",
+            "day1-start" => "day1.start",
+            "day1-target" => "day1.target",
+            "day2-radix" => "day2.radix",
+            "day4-threshold" => "day4.threshold",
+            "day4-neighbourhood" => "day4.neighbourhood",
+            "day4-radius" => "day4.radius",
+            "day8-connections" => "day8.connections",
+            _ => {
+                eprintln!("Unknown option '{argument}'.");
+                return;
+            }
+        };
+        flags.push((key.to_string(), value));
+    }
+
+    let config = match Config::load(&config_path, &flags) {
+        Ok(config) => config,
+        Err(err) => {
+            eprintln!("Invalid configuration: {err}");
             return;
+        }
+    };
+
+    if command == "config" {
+        config.describe();
+        return;
+    }
+    if command == "report" && !markdown && html.is_none() {
+        eprintln!("Missing report format, '--markdown' or '--html <PATH>'.");
+        return;
+    }
+
+    if command == "tui" {
+        config::init(config);
+        if let Err(err) = tui::run(config::get()) {
+            eprintln!("Unable to start the dashboard: {err}");
+        }
+        return;
+    }
+
+    if command == "tool" {
+        config::init(config);
+        tool(&positional, &tool_args);
+        return;
+    }
+
+    let mut positional = positional.into_iter();
+    let Some(selected) = select(positional.next(), positional.next()) else {
+        return;
+    };
+
+    config::init(config);
+    let config = config::get();
+    let mut cache = (command == "run" && config.cache.value).then(Cache::load);
+    let answers = Answers::load(&config.input_dir.value);
+    let mut rows = Vec::new();
+    let mut medians = Vec::new();
+    let mut found_variant = false;
+
+    for (day, parts) in selected {
+        let input = match config.input(day) {
+            Ok(input) => input,
+            Err(err) => {
+                eprintln!("{err}");
+                continue;
+            }
         };
-        let part = part.and_then(|part| {
+        for check in days::CHECKS.iter().filter(|check| check.day == day) {
+            for warning in (check.warnings)(&input) {
+                eprintln!("Warning: {warning}");
+            }
+        }
+
+        for part in parts {
+            let solution = match &variant {
+                Some(name) => {
+                    let Some(found) = days::VARIANTS
+                        .iter()
+                        .find(|v| v.day == day && v.part == part && v.name == name)
+                    else {
+                        continue;
+                    };
+                    found.solve
+                }
+                None => days::SOLUTIONS[day - 1][part - 1],
+            };
+            found_variant = true;
+
+            // only the main implementations are tracked in the timing history
+            match command.as_str() {
+                "bench" => {
+                    let measurement = bench::measure(solution, &input, config.iterations.value);
+                    let lines = input.lines().count();
+                    print_measurement(day, part, variant.as_deref(), &measurement, lines);
+                    if measurement.answer.is_ok() && variant.is_none() {
+                        medians.push((day, part, measurement.median));
+                    }
+                }
+                "report" => {
+                    let measurement = bench::measure(solution, &input, config.iterations.value);
+                    let status = answers.check(day, part, measurement.answer.as_deref().ok());
+                    if measurement.answer.is_ok() && variant.is_none() {
+                        medians.push((day, part, measurement.median));
+                    }
+                    rows.push(report::Row {
+                        day,
+                        part,
+                        status,
+                        measurement,
+                    });
+                }
+                _ => {
+                    let variant = variant.as_deref();
+                    run(solution, day, part, variant, &input, config, cache.as_mut())
+                }
+            }
+        }
+    }
+
+    if let Some(name) = &variant
+        && !found_variant
+    {
+        eprintln!("No variant '{name}' for the selected parts.");
+    }
+
+    if !medians.is_empty() {
+        history::record(&medians);
+    }
+
+    if command == "report" && markdown {
+        let table = report::markdown(&rows);
+        match readme {
+            Some(path) => match update_readme(&path, &table) {
+                Ok(()) => println!("Updated timings in {}", path.display()),
+                Err(err) => eprintln!("Unable to update {}: {err}", path.display()),
+            },
+            None => print!("{table}"),
+        }
+    }
+    if command == "report"
+        && let Some(path) = html
+    {
+        let page = report::html(&rows, &history::load());
+        match fs::write(&path, page) {
+            Ok(()) => println!("Wrote report to {}", path.display()),
+            Err(err) => eprintln!("Unable to write {}: {err}", path.display()),
+        }
+    }
+}
+
+fn tool(positional: &[String], args: &[String]) {
+    let [day, name] = positional else {
+        println!("Available tools:");
+        for tool in days::TOOLS {
+            println!("  {} {:<12} {}", tool.day, tool.name, tool.about);
+        }
+        return;
+    };
+
+    let Some(tool) = days::TOOLS
+        .iter()
+        .find(|tool| tool.day.to_string() == *day && tool.name == name)
+    else {
+        eprintln!("No tool '{name}' for day '{day}'. Run 'aoc2025 tool' to list them.");
+        return;
+    };
+
+    if let Err(err) = (tool.run)(args) {
+        eprintln!("{err}");
+    }
+}
+
+/// Parse and validate the day and part arguments, returning the parts to run for each day
+fn select(day: Option<String>, part: Option<String>) -> Option<Vec<(usize, Vec<usize>)>> {
+    let Some(day) = day else {
+        // No day provided, go through all solutions
+        return Some((1..=days::COUNT).map(|day| (day, vec![1, 2])).collect());
+    };
+
+    let Ok(day @ 1..=days::COUNT) = day.parse::<usize>() else {
+        eprintln!(
+            "Invalid input '{day}' for day. Must be between 1 and {}.",
+            days::COUNT
+        );
+        return None;
+    };
+
+    match part {
+        Some(part) => {
             let Ok(part @ 1..=2) = part.parse::<usize>() else {
                 eprintln!("Invalid input '{part}' for part. Must be between 1 and 2.");
                 return None;
             };
-            Some(part)
-        });
+            Some(vec![(day, vec![part])])
+        }
+        None => Some(vec![(day, vec![1, 2])]),
+    }
+}
+
+fn run(
+    solution: fn(&str) -> Result<String, String>,
+    day: usize,
+    part: usize,
+    variant: Option<&str>,
+    input: &str,
+    config: &Config,
+    cache: Option<&mut Cache>,
+) {
+    let key = cache.as_ref().map(|cache| {
+        let settings = config.variant(day, part);
+        let variant = match variant {
+            Some(name) => format!("{name}/{settings}"),
+            None => settings,
+        };
+        cache.key(day, part, variant, input)
+    });
+    if let (Some(cache), Some(key)) = (&cache, &key)
+        && let Some(answer) = cache.get(key)
+    {
+        report(
+            day,
+            part,
+            Outcome::Cached(answer.to_string()),
+            config.format.value,
+        );
+        return;
+    }
+
+    let outcome = runner::execute(solution, Arc::from(input), config.timeout.value);
+
+    if let (Some(cache), Some(key), Outcome::Answer(answer, _)) = (cache, key, &outcome) {
+        cache.insert(key, answer.clone());
+    }
+
+    report(day, part, outcome, config.format.value);
+}
 
-        // Run specified solution
-        match part {
-            Some(part) => run(days::SOLUTIONS[day - 1][part - 1], day, part),
-            None => {
-                run(days::SOLUTIONS[day - 1][0], day, 1);
-                run(days::SOLUTIONS[day - 1][1], day, 2);
+fn report(day: usize, part: usize, outcome: Outcome, format: Format) {
+    match format {
+        Format::Text => {
+            println!("Day {day}, Part {part}:");
+            match outcome {
+                Outcome::Answer(answer, elapsed) => {
+                    println!("{answer}");
+                    println!("{}ms elapsed", elapsed.as_millis());
+                }
+                Outcome::Cached(answer) => {
+                    println!("{answer}");
+                    println!("cached");
+                }
+                Outcome::TimedOut(timeout) => {
+                    println!("timed out after {}s", timeout.as_secs_f64())
+                }
+                Outcome::Error(err) => println!("error: {err}"),
+                Outcome::Failed => println!("failed"),
             }
         }
-    } else {
-        // No arguments provided, go through all solutions
-        for (n, [part1, part2]) in days::SOLUTIONS.iter().enumerate() {
-            let day = n + 1;
-            run(*part1, day, 1);
-            run(*part2, day, 2);
+        Format::Json => {
+            let fields = match outcome {
+                Outcome::Answer(answer, elapsed) => format!(
+                    r#""status":"ok","answer":"{}","elapsed_ms":{}"#,
+                    json_escape(&answer),
+                    elapsed.as_millis()
+                ),
+                Outcome::Cached(answer) => format!(
+                    r#""status":"ok","answer":"{}","cached":true"#,
+                    json_escape(&answer)
+                ),
+                Outcome::TimedOut(timeout) => {
+                    format!(r#""status":"timeout","elapsed_ms":{}"#, timeout.as_millis())
+                }
+                Outcome::Error(err) => {
+                    format!(r#""status":"error","error":"{}""#, json_escape(&err))
+                }
+                Outcome::Failed => r#""status":"failed""#.to_string(),
+            };
+            println!(r#"{{"day":{day},"part":{part},{fields}}}"#);
+        }
+    }
+}
+
+/// Escape text for the inside of a JSON string
+fn json_escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            // JSON only requires escaping the control characters below a space
+            c if c < ' ' => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
         }
+    }
+    escaped
+}
+
+fn update_readme(path: &Path, table: &str) -> Result<(), String> {
+    let readme = fs::read_to_string(path).map_err(|err| err.to_string())?;
+    let readme = report::splice(&readme, table)?;
+    fs::write(path, readme).map_err(|err| err.to_string())
+}
+
+fn print_measurement(
+    day: usize,
+    part: usize,
+    variant: Option<&str>,
+    measurement: &bench::Measurement,
+    lines: usize,
+) {
+    let label = match variant {
+        Some(name) => format!("Day {day}, Part {part} ({name})"),
+        None => format!("Day {day}, Part {part}"),
+    };
+    if let Err(err) = &measurement.answer {
+        println!("{label}: failed, {err}");
         return;
     }
+    println!(
+        "{label}: median {}, min {}, {} allocations, {:.0} lines/sec ({} runs)",
+        bench::format_duration(measurement.median),
+        bench::format_duration(measurement.min),
+        measurement.allocations,
+        lines as f64 / measurement.median.as_secs_f64(),
+        measurement.iterations
+    );
 }
 
-fn run(solution: fn(), day: usize, part: usize) {
-    use std::time::Instant;
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    println!("Day {day}, Part {part}:");
-    let now = Instant::now();
-    solution();
-    let elapsed = now.elapsed().as_millis();
-    println!("{elapsed}ms elapsed");
+    #[test]
+    fn json_strings() {
+        assert_eq!(json_escape("1234"), "1234");
+        assert_eq!(json_escape("say \"hi\"\\"), r#"say \"hi\"\\"#);
+        assert_eq!(json_escape("a\nb\tc\u{1b}"), r#"a\nb\u0009c\u001b"#);
+        // anything else is valid inside a JSON string as it is
+        assert_eq!(json_escape("'é' at column 3"), "'é' at column 3");
+    }
 }
@@ -0,0 +1,252 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use crate::answers::Status;
+use crate::bench::{self, Measurement};
+
+/// Comments marking where the timing table goes in a README
+pub const START_MARKER: &str = "<!-- timings:start -->";
+pub const END_MARKER: &str = "<!-- timings:end -->";
+
+pub struct Row {
+    pub day: usize,
+    pub part: usize,
+    pub status: Status,
+    pub measurement: Measurement,
+}
+
+/// Build a markdown table of the timings. The answers themselves are left out so the table can
+/// be published.
+pub fn markdown(rows: &[Row]) -> String {
+    let mut table = String::from(
+        "| Day | Part | Answer | Median | Allocations |\n\
+         |----:|-----:|:------:|-------:|------------:|\n",
+    );
+
+    for row in rows {
+        let (median, allocations) = match row.status {
+            Status::Failed => ("-".to_string(), "-".to_string()),
+            _ => (
+                bench::format_duration(row.measurement.median),
+                row.measurement.allocations.to_string(),
+            ),
+        };
+        table.push_str(&format!(
+            "| {} | {} | {} | {median} | {allocations} |\n",
+            row.day, row.part, row.status
+        ));
+    }
+
+    table
+}
+
+/// Replace everything between the start and end markers in `document` with `table`, keeping the
+/// markers themselves
+pub fn splice(document: &str, table: &str) -> Result<String, String> {
+    let start = document
+        .find(START_MARKER)
+        .ok_or_else(|| format!("missing '{START_MARKER}'"))?
+        + START_MARKER.len();
+    let end = document[start..]
+        .find(END_MARKER)
+        .ok_or_else(|| format!("missing '{END_MARKER}' after '{START_MARKER}'"))?
+        + start;
+
+    Ok(format!(
+        "{}\n{table}{}",
+        &document[..start],
+        &document[end..]
+    ))
+}
+
+const BAR_WIDTH: f64 = 400.0;
+const BAR_HEIGHT: f64 = 18.0;
+const SPARK_WIDTH: f64 = 120.0;
+const SPARK_HEIGHT: f64 = 20.0;
+/// Number of most recent runs shown in each sparkline
+const SPARK_RUNS: usize = 30;
+
+const STYLE: &str = "body { font-family: sans-serif; margin: 2em; color: #222; }
+table { border-collapse: collapse; margin-bottom: 0.5em; }
+th, td { padding: 0.2em 0.8em; text-align: right; }
+.correct { color: #1a7f37; }
+.wrong, .failed { color: #cf222e; font-weight: bold; }
+.unverified { color: #6e7781; }
+svg { display: block; }
+rect { fill: #4078c0; }
+polyline { fill: none; stroke: #4078c0; stroke-width: 1.5; }";
+
+/// Build a standalone HTML page with a bar chart of each day's timings and a sparkline of each
+/// part's timing history. Everything is inline so the page can be opened offline.
+pub fn html(rows: &[Row], history: &HashMap<(usize, usize), Vec<Duration>>) -> String {
+    let slowest = rows
+        .iter()
+        .map(|row| row.measurement.median)
+        .max()
+        .unwrap_or_default();
+
+    let mut page = format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <title>Advent of Code 2025 timings</title>\n<style>\n{STYLE}\n</style>\n</head>\n\
+         <body>\n<h1>Advent of Code 2025 timings</h1>\n"
+    );
+
+    for day in rows.chunk_by(|a, b| a.day == b.day) {
+        page.push_str(&format!("<section>\n<h2>Day {}</h2>\n", day[0].day));
+
+        // one bar per part, on a log scale shared by every day so they can be compared
+        let height = BAR_HEIGHT * day.len() as f64;
+        page.push_str(&format!(
+            "<svg width=\"{BAR_WIDTH}\" height=\"{height}\" role=\"img\">\n"
+        ));
+        for (n, row) in day.iter().enumerate() {
+            let width = bar_length(row.measurement.median, slowest);
+            let y = BAR_HEIGHT * n as f64;
+            page.push_str(&format!(
+                "<rect x=\"0\" y=\"{y}\" width=\"{width:.1}\" height=\"{:.1}\">\
+                 <title>Part {} {}</title></rect>\n",
+                BAR_HEIGHT - 4.0,
+                row.part,
+                bench::format_duration(row.measurement.median)
+            ));
+        }
+        page.push_str("</svg>\n");
+
+        page.push_str(
+            "<table>\n<tr><th>Part</th><th>Answer</th><th>Median</th><th>Allocations</th>\
+             <th>History</th></tr>\n",
+        );
+        for row in day {
+            let (median, allocations) = match row.status {
+                Status::Failed => ("-".to_string(), "-".to_string()),
+                _ => (
+                    bench::format_duration(row.measurement.median),
+                    row.measurement.allocations.to_string(),
+                ),
+            };
+            let runs = history
+                .get(&(row.day, row.part))
+                .map(Vec::as_slice)
+                .unwrap_or_default();
+            page.push_str(&format!(
+                "<tr><td>{}</td><td class=\"{status}\">{status}</td><td>{median}</td>\
+                 <td>{allocations}</td><td>{}</td></tr>\n",
+                row.part,
+                sparkline(runs),
+                status = row.status,
+            ));
+        }
+        page.push_str("</table>\n</section>\n");
+    }
+
+    page.push_str("</body>\n</html>\n");
+    page
+}
+
+/// Length of a bar on a log scale starting at 1µs, so both microsecond and multi-second parts
+/// are visible
+fn bar_length(median: Duration, slowest: Duration) -> f64 {
+    let scale = |duration: Duration| (1.0 + duration.as_secs_f64() * 1e6).log10();
+    let length = BAR_WIDTH * scale(median) / scale(slowest).max(f64::EPSILON);
+    length.max(1.0)
+}
+
+fn sparkline(runs: &[Duration]) -> String {
+    let runs = &runs[runs.len().saturating_sub(SPARK_RUNS)..];
+    if runs.len() < 2 {
+        return "-".to_string();
+    }
+
+    let secs: Vec<_> = runs.iter().map(Duration::as_secs_f64).collect();
+    let min = secs.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = secs.iter().copied().fold(0.0, f64::max);
+    let step = SPARK_WIDTH / (secs.len() - 1) as f64;
+
+    let points: Vec<_> = secs
+        .iter()
+        .enumerate()
+        .map(|(n, secs)| {
+            // flat history is drawn through the middle
+            let y = if max > min {
+                SPARK_HEIGHT - (secs - min) / (max - min) * SPARK_HEIGHT
+            } else {
+                SPARK_HEIGHT / 2.0
+            };
+            format!("{:.1},{y:.1}", n as f64 * step)
+        })
+        .collect();
+
+    format!(
+        "<svg width=\"{SPARK_WIDTH}\" height=\"{SPARK_HEIGHT}\"><polyline points=\"{}\"/></svg>",
+        points.join(" ")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn row(day: usize, part: usize, status: Status) -> Row {
+        Row {
+            day,
+            part,
+            status,
+            measurement: Measurement {
+                answer: Ok("42".to_string()),
+                median: Duration::from_micros(1500),
+                min: Duration::from_micros(1400),
+                allocations: 7,
+                iterations: 10,
+            },
+        }
+    }
+
+    #[test]
+    fn table() {
+        let table = markdown(&[row(1, 1, Status::Correct), row(1, 2, Status::Failed)]);
+        let lines: Vec<_> = table.lines().collect();
+        assert_eq!(lines.len(), 4);
+        assert_eq!(lines[2], "| 1 | 1 | correct | 1.50ms | 7 |");
+        assert_eq!(lines[3], "| 1 | 2 | failed | - | - |");
+        assert!(!table.contains("42"));
+    }
+
+    #[test]
+    fn splicing() {
+        let readme = "# Timings\n<!-- timings:start -->\nold\n<!-- timings:end -->\nfooter\n";
+        assert_eq!(
+            splice(readme, "new\n").unwrap(),
+            "# Timings\n<!-- timings:start -->\nnew\n<!-- timings:end -->\nfooter\n"
+        );
+        assert!(splice("# Timings\n", "new\n").is_err());
+        assert!(splice("<!-- timings:end --><!-- timings:start -->", "new\n").is_err());
+    }
+
+    #[test]
+    fn html_page() {
+        let rows = [
+            row(1, 1, Status::Correct),
+            row(1, 2, Status::Wrong),
+            row(2, 1, Status::Unverified),
+        ];
+        let history = HashMap::from([(
+            (1, 1),
+            vec![Duration::from_micros(2000), Duration::from_micros(1500)],
+        )]);
+        let page = html(&rows, &history);
+
+        assert_eq!(page.matches("<section>").count(), 2);
+        assert_eq!(page.matches("<rect").count(), 3);
+        assert_eq!(page.matches("<polyline").count(), 1);
+        assert!(page.contains(r#"<td class="wrong">wrong</td>"#));
+        assert!(!page.contains("http") && !page.contains("src="));
+    }
+
+    #[test]
+    fn sparklines() {
+        assert_eq!(sparkline(&[Duration::from_micros(5)]), "-");
+        let flat = sparkline(&[Duration::from_micros(5), Duration::from_micros(5)]);
+        assert!(flat.contains(r#"points="0.0,10.0 120.0,10.0""#));
+    }
+}
@@ -0,0 +1,42 @@
+use std::sync::Arc;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+pub enum Outcome {
+    Answer(String, Duration),
+    Cached(String),
+    TimedOut(Duration),
+    /// The solution returned an error rather than an answer, such as for invalid input
+    Error(String),
+    Failed,
+}
+
+/// Run a solution on a separate thread so we can stop waiting if it takes too long. A solution
+/// that times out is left running in the background until the program exits.
+pub fn execute(
+    solution: fn(&str) -> Result<String, String>,
+    input: Arc<str>,
+    timeout: Option<Duration>,
+) -> Outcome {
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        let now = Instant::now();
+        let answer = solution(&input);
+        let _ = sender.send((answer, now.elapsed()));
+    });
+
+    match timeout {
+        Some(timeout) => match receiver.recv_timeout(timeout) {
+            Ok((Ok(answer), elapsed)) => Outcome::Answer(answer, elapsed),
+            Ok((Err(err), _)) => Outcome::Error(err),
+            Err(RecvTimeoutError::Timeout) => Outcome::TimedOut(timeout),
+            Err(RecvTimeoutError::Disconnected) => Outcome::Failed,
+        },
+        None => match receiver.recv() {
+            Ok((Ok(answer), elapsed)) => Outcome::Answer(answer, elapsed),
+            Ok((Err(err), _)) => Outcome::Error(err),
+            Err(_) => Outcome::Failed,
+        },
+    }
+}
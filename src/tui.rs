@@ -0,0 +1,292 @@
+use std::fs::File;
+use std::io::{self, Read, Write};
+use std::panic;
+use std::process::{Command, Stdio};
+use std::sync::Arc;
+use std::sync::mpsc::{self, Sender};
+use std::thread;
+use std::time::Duration;
+
+use crate::answers::{Answers, Status};
+use crate::bench;
+use crate::config::Config;
+use crate::days;
+use crate::runner::{self, Outcome};
+
+const HELP: &str = "up/down or k/j select a day   r rerun   enter show output   q quit";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+    Pending,
+    Running,
+    Pass,
+    Fail,
+    Timeout,
+    /// Finished, but there's no accepted answer to compare against
+    Done,
+}
+
+impl State {
+    fn label(self) -> &'static str {
+        match self {
+            State::Pending => "pending",
+            State::Running => "running",
+            State::Pass => "pass",
+            State::Fail => "fail",
+            State::Timeout => "timeout",
+            State::Done => "done",
+        }
+    }
+
+    fn colour(self) -> &'static str {
+        match self {
+            State::Pending => "\x1b[90m",
+            State::Running => "\x1b[33m",
+            State::Pass => "\x1b[32m",
+            State::Fail | State::Timeout => "\x1b[31m",
+            State::Done => "\x1b[36m",
+        }
+    }
+}
+
+struct Row {
+    state: State,
+    elapsed: Option<Duration>,
+    output: String,
+}
+
+enum Key {
+    Up,
+    Down,
+    Rerun,
+    Output,
+    Quit,
+}
+
+enum Event {
+    Key(Key),
+    Started(usize),
+    Finished(usize, Outcome),
+}
+
+/// Puts the terminal into raw mode on the alternate screen, restoring it when dropped
+struct Terminal {
+    saved: String,
+}
+
+impl Terminal {
+    fn enter() -> Result<Terminal, String> {
+        let saved = stty(&["-g"])?;
+        stty(&["raw", "-echo"])?;
+        print!("\x1b[?1049h\x1b[?25l");
+        Ok(Terminal {
+            saved: saved.trim().to_string(),
+        })
+    }
+}
+
+impl Drop for Terminal {
+    fn drop(&mut self) {
+        print!("\x1b[?25h\x1b[?1049l");
+        let _ = io::stdout().flush();
+        let _ = stty(&[&self.saved]);
+    }
+}
+
+fn stty(args: &[&str]) -> Result<String, String> {
+    let tty = File::open("/dev/tty").map_err(|err| format!("no terminal available: {err}"))?;
+    let output = Command::new("stty")
+        .args(args)
+        .stdin(tty)
+        .stderr(Stdio::null())
+        .output()
+        .map_err(|err| format!("unable to run stty: {err}"))?;
+    if !output.status.success() {
+        return Err("unable to configure the terminal".to_string());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Interactive view of every part, run one after another in the background. Rows are ordered by
+/// day then part, so the row for a part is `(day - 1) * 2 + (part - 1)`.
+pub fn run(config: &Config) -> Result<(), String> {
+    let answers = Answers::load(&config.input_dir.value);
+    let inputs: Vec<_> = (1..=days::COUNT).map(|day| config.input(day)).collect();
+
+    let mut rows: Vec<_> = (0..days::COUNT * 2)
+        .map(|n| Row {
+            state: if inputs[n / 2].is_ok() {
+                State::Pending
+            } else {
+                State::Fail
+            },
+            elapsed: None,
+            output: inputs[n / 2].as_ref().err().cloned().unwrap_or_default(),
+        })
+        .collect();
+    let inputs: Vec<_> = inputs
+        .into_iter()
+        .map(|input| input.ok().map(Arc::<str>::from))
+        .collect();
+
+    let terminal = Terminal::enter()?;
+    // solution panics would otherwise be printed over the screen, they are shown as failures
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+
+    let (events, receiver) = mpsc::channel();
+    read_keys(events.clone());
+    let jobs = run_jobs(inputs.clone(), config.timeout.value, events);
+    for (n, row) in rows.iter().enumerate() {
+        if row.state == State::Pending {
+            let _ = jobs.send(n);
+        }
+    }
+
+    let mut selected = 0;
+    let mut show_output = false;
+    draw(&rows, selected, show_output);
+
+    for event in receiver {
+        match event {
+            Event::Key(Key::Quit) => break,
+            Event::Key(Key::Up) => selected = selected.saturating_sub(1),
+            Event::Key(Key::Down) => selected = (selected + 1).min(days::COUNT - 1),
+            Event::Key(Key::Output) => show_output = !show_output,
+            Event::Key(Key::Rerun) => {
+                // days whose input couldn't be read stay failed
+                for n in [selected * 2, selected * 2 + 1] {
+                    if !matches!(rows[n].state, State::Pending | State::Running)
+                        && inputs[selected].is_some()
+                    {
+                        rows[n].state = State::Pending;
+                        let _ = jobs.send(n);
+                    }
+                }
+            }
+            Event::Started(n) => rows[n].state = State::Running,
+            Event::Finished(n, outcome) => {
+                let (day, part) = (n / 2 + 1, n % 2 + 1);
+                let row = &mut rows[n];
+                match outcome {
+                    Outcome::Answer(answer, elapsed) => {
+                        row.state = match answers.check(day, part, Some(&answer)) {
+                            Status::Correct => State::Pass,
+                            Status::Unverified => State::Done,
+                            Status::Wrong | Status::Failed => State::Fail,
+                        };
+                        row.elapsed = Some(elapsed);
+                        row.output = answer;
+                    }
+                    Outcome::TimedOut(timeout) => {
+                        row.state = State::Timeout;
+                        row.elapsed = Some(timeout);
+                        row.output = format!("timed out after {}s", timeout.as_secs_f64());
+                    }
+                    Outcome::Error(err) => {
+                        row.state = State::Fail;
+                        row.elapsed = None;
+                        row.output = err;
+                    }
+                    Outcome::Failed | Outcome::Cached(_) => {
+                        row.state = State::Fail;
+                        row.elapsed = None;
+                        row.output = "panicked".to_string();
+                    }
+                }
+            }
+        }
+        draw(&rows, selected, show_output);
+    }
+
+    panic::set_hook(hook);
+    drop(terminal);
+    Ok(())
+}
+
+/// Run queued rows one at a time on a background thread, reporting progress as events
+fn run_jobs(
+    inputs: Vec<Option<Arc<str>>>,
+    timeout: Option<Duration>,
+    events: Sender<Event>,
+) -> Sender<usize> {
+    let (jobs, queue) = mpsc::channel::<usize>();
+    thread::spawn(move || {
+        for n in queue {
+            let (day, part) = (n / 2 + 1, n % 2 + 1);
+            let Some(input) = &inputs[day - 1] else {
+                continue;
+            };
+            let _ = events.send(Event::Started(n));
+            let solution = days::SOLUTIONS[day - 1][part - 1];
+            let outcome = runner::execute(solution, input.clone(), timeout);
+            if events.send(Event::Finished(n, outcome)).is_err() {
+                break;
+            }
+        }
+    });
+    jobs
+}
+
+fn read_keys(events: Sender<Event>) {
+    thread::spawn(move || {
+        let mut bytes = io::stdin().lock().bytes().map_while(Result::ok);
+        while let Some(byte) = bytes.next() {
+            let key = match byte {
+                b'k' => Key::Up,
+                b'j' => Key::Down,
+                b'r' => Key::Rerun,
+                b'\r' | b'\n' | b' ' => Key::Output,
+                // ctrl-c is just another byte in raw mode
+                b'q' | 3 => Key::Quit,
+                // arrow keys arrive as ESC [ A and ESC [ B
+                0x1b => match (bytes.next(), bytes.next()) {
+                    (Some(b'['), Some(b'A')) => Key::Up,
+                    (Some(b'['), Some(b'B')) => Key::Down,
+                    _ => continue,
+                },
+                _ => continue,
+            };
+            if events.send(Event::Key(key)).is_err() {
+                break;
+            }
+        }
+    });
+}
+
+fn draw(rows: &[Row], selected: usize, show_output: bool) {
+    // raw mode doesn't translate newlines, so every line ends with an explicit carriage return
+    let mut screen = String::from("\x1b[2J\x1b[H\x1b[1mAdvent of Code 2025\x1b[0m\r\n\r\n");
+    screen.push_str(" Day  Part  Status    Time\r\n");
+
+    for (n, row) in rows.iter().enumerate() {
+        let (day, part) = (n / 2 + 1, n % 2 + 1);
+        let day_label = if part == 1 {
+            day.to_string()
+        } else {
+            String::new()
+        };
+        let time = row.elapsed.map(bench::format_duration).unwrap_or_default();
+        let highlight = if n / 2 == selected { "\x1b[7m" } else { "" };
+        screen.push_str(&format!(
+            "{highlight} {day_label:<4} {part:<5} {}{:<9}\x1b[39m {time:<10}\x1b[0m\r\n",
+            row.state.colour(),
+            row.state.label(),
+        ));
+    }
+
+    if show_output {
+        screen.push_str(&format!(
+            "\r\n\x1b[1mDay {} output\x1b[0m\r\n",
+            selected + 1
+        ));
+        for part in 1..=2 {
+            let row = &rows[selected * 2 + part - 1];
+            screen.push_str(&format!("Part {part}: {}\r\n", row.output));
+        }
+    }
+
+    screen.push_str(&format!("\r\n\x1b[90m{HELP}\x1b[0m\r\n"));
+    print!("{screen}");
+    let _ = io::stdout().flush();
+}